@@ -0,0 +1,129 @@
+use lthash_rs::LtHash;
+use std::iter::FromIterator;
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup,
+    BenchmarkId, Criterion, Throughput,
+};
+
+type LtHash16 = lthash_rs::LtHash16<sha3::Shake128>;
+type LtHash20 = lthash_rs::LtHash20<sha3::Shake128>;
+type LtHash32 = lthash_rs::LtHash32<sha3::Shake128>;
+
+/// Number of elements of the snapshots whose differences are benchmarked.
+const SNAPSHOT_LEN: usize = 10_000;
+
+/// Checksums with `nonzero` lanes of `lane_len` bytes, the others being zero.
+fn sparse<L>(lane_len: usize, nonzero: usize) -> L
+where
+    L: LtHash + FromIterator<&'static str> + for<'a> TryFrom<&'a [u8]>,
+{
+    let mut bytes = L::from_iter(["hello-world"]).into_bytes();
+    for lane in bytes.chunks_exact_mut(lane_len).skip(nonzero) {
+        lane.fill(0);
+    }
+    L::try_from(&bytes).ok().unwrap()
+}
+
+/// The difference of two snapshots of `SNAPSHOT_LEN` elements, the second
+/// one with `changes` elements inserted and `changes` removed.
+fn delta<L>(changes: usize) -> L
+where
+    L: LtHash + FromIterator<String>,
+{
+    let before = L::from_iter((0..SNAPSHOT_LEN).map(|i| format!("row-{i}")));
+    let after = L::from_iter(
+        (changes..SNAPSHOT_LEN + changes).map(|i| format!("row-{i}")),
+    );
+    after.difference(&before)
+}
+
+/// Benchmarks the compression of `lthash`, the compressed size being the
+/// parameter of the benchmark ID.
+fn bench<L>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    fixture: &str,
+    lthash: &L,
+    compress: fn(&L) -> Vec<u8>,
+    decompress: fn(&[u8]) -> Result<L, String>,
+) where
+    L: LtHash,
+{
+    let compressed = compress(lthash);
+    let parameter = format!("{fixture}/{}B", compressed.len());
+    group
+        .throughput(Throughput::Bytes(L::BYTE_LEN as u64))
+        .bench_with_input(
+            BenchmarkId::new(format!("compress{name}"), &parameter),
+            lthash,
+            |b, lthash| b.iter(|| compress(lthash)),
+        )
+        .bench_with_input(
+            BenchmarkId::new(format!("into_bytes{name}"), &parameter),
+            lthash,
+            |b, lthash| b.iter(|| lthash.clone().into_bytes()),
+        )
+        .bench_with_input(
+            BenchmarkId::new(format!("decompress{name}"), &parameter),
+            &compressed,
+            |b, compressed| b.iter(|| decompress(compressed)),
+        );
+}
+
+/// Benchmarks `L` with none, 1/64, 1/4 and all of its lanes nonzero, and with
+/// the deltas of 1, 16 and 256 changes.
+fn bench_variant<L>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    lane_len: usize,
+    compress: fn(&L) -> Vec<u8>,
+    decompress: fn(&[u8]) -> Result<L, String>,
+) where
+    L: LtHash
+        + FromIterator<&'static str>
+        + FromIterator<String>
+        + for<'a> TryFrom<&'a [u8]>,
+{
+    let lanes = L::BYTE_LEN / lane_len;
+    for nonzero in [0, lanes / 64, lanes / 4, lanes] {
+        let lthash = sparse::<L>(lane_len, nonzero);
+        let fixture = format!("{nonzero}-lanes");
+        bench(group, name, &fixture, &lthash, compress, decompress);
+    }
+
+    for changes in [1, 16, 256] {
+        let lthash = delta::<L>(changes);
+        let fixture = format!("delta-{changes}");
+        bench(group, name, &fixture, &lthash, compress, decompress);
+    }
+}
+
+fn compression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compression");
+
+    bench_variant::<LtHash16>(
+        &mut group,
+        "16",
+        2,
+        LtHash16::to_compressed_bytes,
+        LtHash16::from_compressed_bytes,
+    );
+    bench_variant::<LtHash20>(
+        &mut group,
+        "20",
+        8,
+        LtHash20::to_compressed_bytes,
+        LtHash20::from_compressed_bytes,
+    );
+    bench_variant::<LtHash32>(
+        &mut group,
+        "32",
+        4,
+        LtHash32::to_compressed_bytes,
+        LtHash32::from_compressed_bytes,
+    );
+}
+
+criterion_group!(benches, compression);
+criterion_main!(benches);
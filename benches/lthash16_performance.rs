@@ -123,6 +123,20 @@ fn insert<M: MeasurementName>(c: &mut Criterion<M>) {
                     })
                 },
             );
+
+        #[cfg(feature = "blake3")]
+        insert_benchmark_group
+            .throughput(Throughput::Bytes(bytes as u64))
+            .bench_with_input(
+                BenchmarkId::new("blake3", bytes),
+                &input,
+                |b, input| {
+                    b.iter(|| {
+                        let mut test = lthash_rs::LtHash16Blake3::new();
+                        test.insert(input)
+                    })
+                },
+            );
     }
 }
 
@@ -0,0 +1,6 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    lthash_rs::LtHash20::<sha3::Shake256>::try_from(data).ok();
+});
@@ -0,0 +1,311 @@
+use std::{
+    fs,
+    io::{self, BufRead, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use lthash_rs::{
+    dynamic::{Variant, Xof},
+    Blake2xb, Expander, LtHash, LtHash16, LtHash20, LtHash32,
+};
+
+/// Computes and manipulates LtHash checksums.
+///
+/// Checksums are read and written as hex strings (as produced by
+/// `to_hex_string`), or as raw bytes (as produced by `into_bytes`) with
+/// `--binary-input` and `--binary`.
+///
+/// Checksums record neither their width nor their hash function: commands
+/// reading them must be given the `--width` and `--xof` they were computed
+/// with. A wrong width is rejected, but a checksum updated with another hash
+/// function is silently corrupted.
+#[derive(Parser)]
+#[command(name = "lthash", version)]
+struct Cli {
+    /// Number of bits per chunk of the checksum.
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_enum,
+        default_value_t = Variant::LtHash16
+    )]
+    width: Variant,
+
+    /// Extendable output function used to hash the elements.
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_enum,
+        default_value_t = Xof::Shake128
+    )]
+    xof: Xof,
+
+    /// Writes checksums as raw bytes instead of hex strings.
+    #[arg(short, long, global = true)]
+    binary: bool,
+
+    /// Reads checksums as raw bytes instead of hex strings.
+    #[arg(long, global = true)]
+    binary_input: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Computes the checksum of a set of elements.
+    ///
+    /// Every line of stdin is an element, unless `--lines` or `--dir` is given.
+    Hash {
+        /// Uses every line of the file as an element.
+        #[arg(long, conflicts_with = "dir")]
+        lines: Option<PathBuf>,
+        /// Uses the content of every file in the directory (recursively) as an
+        /// element. Symbolic links to directories are not followed.
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Inserts elements into a checksum, reading them from stdin if none are
+    /// given.
+    Add {
+        checksum: PathBuf,
+        elements: Vec<String>,
+    },
+    /// Removes elements from a checksum, reading them from stdin if none are
+    /// given.
+    Remove {
+        checksum: PathBuf,
+        elements: Vec<String>,
+    },
+    /// Computes the union of two checksums.
+    Union { left: PathBuf, right: PathBuf },
+    /// Computes the difference of two checksums.
+    Diff { left: PathBuf, right: PathBuf },
+    /// Exits successfully if the two checksums are equal, with status 1
+    /// otherwise.
+    Eq { left: PathBuf, right: PathBuf },
+    /// Prints a short fingerprint (32 bytes) of a checksum.
+    Fingerprint { checksum: PathBuf },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.xof {
+        Xof::Blake2xb => with_xof::<Blake2xb>(&cli),
+        Xof::Shake128 => with_xof::<sha3::Shake128>(&cli),
+        Xof::Shake256 => with_xof::<sha3::Shake256>(&cli),
+        #[cfg(feature = "blake3")]
+        Xof::Blake3 => with_xof::<blake3::Hasher>(&cli),
+        #[cfg(feature = "k12")]
+        Xof::K12 => with_xof::<k12::KangarooTwelve>(&cli),
+        #[cfg(not(feature = "blake3"))]
+        Xof::Blake3 => unreachable!("--xof only accepts the enabled functions"),
+        #[cfg(not(feature = "k12"))]
+        Xof::K12 => unreachable!("--xof only accepts the enabled functions"),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("lthash: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn with_xof<H>(cli: &Cli) -> Result<ExitCode, String>
+where
+    H: Expander + Default,
+{
+    match cli.width {
+        Variant::LtHash16 => run::<LtHash16<H>, H>(cli),
+        Variant::LtHash20 => run::<LtHash20<H>, H>(cli),
+        Variant::LtHash32 => run::<LtHash32<H>, H>(cli),
+    }
+}
+
+fn run<L, H>(cli: &Cli) -> Result<ExitCode, String>
+where
+    L: LtHash + Default + PartialEq + for<'a> TryFrom<&'a [u8], Error = String>,
+    H: Expander + Default,
+{
+    let lthash = match &cli.command {
+        Command::Hash { lines, dir } => {
+            let mut lthash = L::default();
+            match (lines, dir) {
+                (Some(path), _) => {
+                    let file = fs::File::open(path).map_err(io_error(path))?;
+                    for_each_line(io::BufReader::new(file), |line| {
+                        lthash.insert(line)
+                    })
+                    .map_err(io_error(path))?;
+                }
+                (None, Some(dir)) => {
+                    for_each_file(dir, &mut |content| lthash.insert(content))?
+                }
+                (None, None) => {
+                    for_each_line(io::stdin().lock(), |line| {
+                        lthash.insert(line)
+                    })
+                    .map_err(|err| format!("stdin: {err}"))?;
+                }
+            }
+            lthash
+        }
+        Command::Add { checksum, elements } => {
+            let mut lthash: L = read_checksum(checksum, cli.binary_input)?;
+            for_each_element(elements, |element| lthash.insert(element))?;
+            lthash
+        }
+        Command::Remove { checksum, elements } => {
+            let mut lthash: L = read_checksum(checksum, cli.binary_input)?;
+            for_each_element(elements, |element| lthash.remove(element))?;
+            lthash
+        }
+        Command::Union { left, right } => {
+            let left: L = read_checksum(left, cli.binary_input)?;
+            let right: L = read_checksum(right, cli.binary_input)?;
+            left.union(&right)
+        }
+        Command::Diff { left, right } => {
+            let left: L = read_checksum(left, cli.binary_input)?;
+            let right: L = read_checksum(right, cli.binary_input)?;
+            left.difference(&right)
+        }
+        Command::Eq { left, right } => {
+            let left: L = read_checksum(left, cli.binary_input)?;
+            let right: L = read_checksum(right, cli.binary_input)?;
+            return Ok(if left == right {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            });
+        }
+        Command::Fingerprint { checksum } => {
+            let lthash: L = read_checksum(checksum, cli.binary_input)?;
+            let mut fingerprint = [0u8; 32];
+            H::default().expand(&lthash.into_bytes(), &mut fingerprint);
+            let hex: String =
+                fingerprint.iter().map(|b| format!("{b:02x}")).collect();
+            println!("{hex}");
+            return Ok(ExitCode::SUCCESS);
+        }
+    };
+
+    write_checksum(lthash, cli.binary)
+        .map_err(|err| format!("stdout: {err}"))?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn io_error(path: &Path) -> impl Fn(io::Error) -> String + '_ {
+    move |err| format!("{}: {err}", path.display())
+}
+
+/// Calls `f` with every line of `reader`, without the line terminator.
+fn for_each_line(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&[u8]),
+) -> io::Result<()> {
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line)? != 0 {
+        if line.ends_with(b"\n") {
+            line.pop();
+            if line.ends_with(b"\r") {
+                line.pop();
+            }
+        }
+        f(&line);
+        line.clear();
+    }
+
+    Ok(())
+}
+
+/// Calls `f` with the content of every file found (recursively) in `dir`,
+/// skipping the symbolic links to directories.
+fn for_each_file(dir: &Path, f: &mut impl FnMut(&[u8])) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+        let entry = entry.map_err(io_error(dir))?;
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(io_error(&path))?;
+        if file_type.is_dir() {
+            for_each_file(&path, f)?;
+        } else if file_type.is_symlink() && path.is_dir() {
+            // not followed, as they may loop
+            continue;
+        } else {
+            f(&fs::read(&path).map_err(io_error(&path))?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls `f` with every element given on the command line, or with every line
+/// of stdin if there are none.
+fn for_each_element(
+    elements: &[String],
+    mut f: impl FnMut(&[u8]),
+) -> Result<(), String> {
+    if elements.is_empty() {
+        return for_each_line(io::stdin().lock(), f)
+            .map_err(|err| format!("stdin: {err}"));
+    }
+
+    for element in elements {
+        f(element.as_bytes());
+    }
+
+    Ok(())
+}
+
+/// Reads a checksum stored as raw bytes if `binary` is set, as an hex string
+/// otherwise.
+fn read_checksum<L>(path: &Path, binary: bool) -> Result<L, String>
+where
+    L: for<'a> TryFrom<&'a [u8], Error = String>,
+{
+    let mut content = Vec::new();
+    fs::File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut content))
+        .map_err(io_error(path))?;
+
+    if !binary {
+        content = decode_hex(content.trim_ascii()).ok_or_else(|| {
+            format!("{}: not a valid checksum.", path.display())
+        })?;
+    }
+
+    L::try_from(content.as_ref())
+        .map_err(|err| format!("{}: {err}", path.display()))
+}
+
+fn decode_hex(hex: &[u8]) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    hex.chunks_exact(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).ok()?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
+
+fn write_checksum(lthash: impl LtHash, binary: bool) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    if binary {
+        stdout.write_all(&lthash.into_bytes())?;
+    } else {
+        writeln!(stdout, "{}", lthash.to_hex_string())?;
+    }
+    stdout.flush()
+}
@@ -0,0 +1,429 @@
+use digest::{Update, XofReader};
+
+use crate::XofExpander;
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const BLOCK_LEN: usize = 128;
+const OUT_LEN: usize = 64;
+
+/// The parameter block of a BLAKE2b instance.
+#[derive(Clone)]
+struct Params {
+    digest_length: u8,
+    key_length: u8,
+    fanout: u8,
+    depth: u8,
+    leaf_length: u32,
+    node_offset: u32,
+    xof_length: u32,
+    node_depth: u8,
+    inner_length: u8,
+    salt: [u8; 16],
+    personal: [u8; 16],
+}
+
+impl Params {
+    fn to_words(&self) -> [u64; 8] {
+        let mut block = [0u8; 64];
+        block[0] = self.digest_length;
+        block[1] = self.key_length;
+        block[2] = self.fanout;
+        block[3] = self.depth;
+        block[4..8].copy_from_slice(&self.leaf_length.to_le_bytes());
+        block[8..12].copy_from_slice(&self.node_offset.to_le_bytes());
+        block[12..16].copy_from_slice(&self.xof_length.to_le_bytes());
+        block[16] = self.node_depth;
+        block[17] = self.inner_length;
+        block[32..48].copy_from_slice(&self.salt);
+        block[48..64].copy_from_slice(&self.personal);
+
+        let mut words = [0u64; 8];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        words
+    }
+}
+
+/// A BLAKE2b instance, following RFC 7693.
+#[derive(Clone)]
+struct Blake2b {
+    h: [u64; 8],
+    buffer: [u8; BLOCK_LEN],
+    buffer_len: usize,
+    counter: u128,
+    digest_length: usize,
+}
+
+impl Blake2b {
+    fn new(params: &Params, key: &[u8]) -> Self {
+        let mut h = IV;
+        for (h, param) in h.iter_mut().zip(params.to_words()) {
+            *h ^= param;
+        }
+
+        let mut this = Self {
+            h,
+            buffer: [0; BLOCK_LEN],
+            buffer_len: 0,
+            counter: 0,
+            digest_length: params.digest_length as usize,
+        };
+
+        if !key.is_empty() {
+            let mut block = [0u8; BLOCK_LEN];
+            block[..key.len()].copy_from_slice(key);
+            this.update(&block);
+        }
+
+        this
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // the last block is compressed by `finalize`
+            if self.buffer_len == BLOCK_LEN {
+                self.counter += BLOCK_LEN as u128;
+                compress(&mut self.h, &self.buffer, self.counter, false);
+                self.buffer_len = 0;
+            }
+
+            let len = data.len().min(BLOCK_LEN - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + len]
+                .copy_from_slice(&data[..len]);
+            self.buffer_len += len;
+            data = &data[len..];
+        }
+    }
+
+    fn finalize(mut self, out: &mut [u8]) {
+        self.counter += self.buffer_len as u128;
+        self.buffer[self.buffer_len..].fill(0);
+        compress(&mut self.h, &self.buffer, self.counter, true);
+
+        let mut digest = [0u8; OUT_LEN];
+        for (bytes, h) in digest.chunks_exact_mut(8).zip(self.h) {
+            bytes.copy_from_slice(&h.to_le_bytes());
+        }
+        out.copy_from_slice(&digest[..self.digest_length]);
+    }
+}
+
+fn compress(
+    h: &mut [u64; 8],
+    block: &[u8; BLOCK_LEN],
+    counter: u128,
+    last: bool,
+) {
+    let mut m = [0u64; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *m = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= counter as u64;
+    v[13] ^= (counter >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    for s in SIGMA.iter().cycle().take(12) {
+        mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+#[inline(always)]
+fn mix(
+    v: &mut [u64; 16],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    x: u64,
+    y: u64,
+) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+#[derive(Clone)]
+enum State {
+    /// The output length is not known yet, so the whole input is kept in
+    /// memory until then.
+    Deferred(Vec<u8>),
+    /// The root instance, hashing the input as it comes.
+    Root(Blake2b),
+}
+
+/// The BLAKE2xb extendable output function, as used by folly's `LtHash`.
+///
+/// The output of BLAKE2xb depends on its length, which has to be known
+/// before hashing the input. [`finalize_xof`](Blake2xb::finalize_xof) produces
+/// an output of the length given to [`Blake2xb::new`], of unknown length if
+/// none is given.
+///
+/// As an [`Expander`](crate::Expander) (or [`XofExpander`]), which is how the
+/// LtHash types use it, the output length is the size of the checksum: if it
+/// is not given to [`Blake2xb::new`], the input is buffered until then, the
+/// whole of it being kept in memory, so giving the length upfront is better
+/// for large inputs (e.g. hashed through `stream`).
+///
+/// It does not implement `ExtendableOutput`, whose
+/// [`finalize_xof_into`](digest::ExtendableOutput::finalize_xof_into) would
+/// have to return the beginning of an output of unknown length, unlike the
+/// expansions of the checksums.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{Blake2xb, LtHash, LtHash16};
+/// let mut lthash = LtHash16::<Blake2xb>::new();
+/// lthash.insert("hello");
+/// ```
+#[derive(Clone)]
+pub struct Blake2xb {
+    params: Params,
+    key: Vec<u8>,
+    state: State,
+}
+
+impl Blake2xb {
+    /// The output length announcing an output of unknown length.
+    pub const UNKNOWN_OUTPUT_LEN: u32 = u32::MAX;
+
+    /// Creates a BLAKE2xb instance with an optional key (up to 64 bytes), salt
+    /// and personalization (up to 16 bytes each).
+    ///
+    /// Unless `output_len` is [`None`], the output must be exactly
+    /// `output_len` bytes long, or [`Blake2xb::UNKNOWN_OUTPUT_LEN`] for an
+    /// output of unknown length.
+    ///
+    /// # Panics
+    /// Expanding an element panics unless `output_len` is [`None`] or the
+    /// size of the checksum (2048 bytes for `LtHash16`, 2688 for `LtHash20`
+    /// and 4096 for `LtHash32`).
+    pub fn new(
+        output_len: Option<u32>,
+        key: &[u8],
+        salt: &[u8],
+        personalization: &[u8],
+    ) -> Result<Self, String> {
+        if key.len() > 64 {
+            return Err(String::from("Key longer than 64 bytes."));
+        }
+        if salt.len() > 16 {
+            return Err(String::from("Salt longer than 16 bytes."));
+        }
+        if personalization.len() > 16 {
+            return Err(String::from("Personalization longer than 16 bytes."));
+        }
+        if output_len == Some(0) {
+            return Err(String::from("Empty output."));
+        }
+
+        let mut params = Params {
+            digest_length: OUT_LEN as u8,
+            key_length: key.len() as u8,
+            fanout: 1,
+            depth: 1,
+            leaf_length: 0,
+            node_offset: 0,
+            xof_length: Self::UNKNOWN_OUTPUT_LEN,
+            node_depth: 0,
+            inner_length: 0,
+            salt: [0; 16],
+            personal: [0; 16],
+        };
+        params.salt[..salt.len()].copy_from_slice(salt);
+        params.personal[..personalization.len()]
+            .copy_from_slice(personalization);
+
+        let state = match output_len {
+            Some(output_len) => {
+                params.xof_length = output_len;
+                State::Root(Blake2b::new(&params, key))
+            }
+            None => State::Deferred(Vec::new()),
+        };
+
+        Ok(Self {
+            params,
+            key: key.to_vec(),
+            state,
+        })
+    }
+
+    /// Finalizes the hash, returning the reader of an output of the length
+    /// given to [`Blake2xb::new`], of unknown length if none was given.
+    pub fn finalize_xof(self) -> Blake2xbReader {
+        let output_len = self.params.xof_length;
+        self.finalize_root(output_len)
+    }
+
+    fn finalize_root(self, output_len: u32) -> Blake2xbReader {
+        let mut params = self.params;
+        let root = match self.state {
+            State::Root(root) => root,
+            State::Deferred(input) => {
+                params.xof_length = output_len;
+                let mut root = Blake2b::new(&params, &self.key);
+                root.update(&input);
+                root
+            }
+        };
+
+        let mut hash = [0u8; OUT_LEN];
+        root.finalize(&mut hash);
+
+        // the parameters of the expansion instances, besides digest length and
+        // node offset
+        params.key_length = 0;
+        params.fanout = 0;
+        params.depth = 0;
+        params.leaf_length = OUT_LEN as u32;
+        params.node_depth = 0;
+        params.inner_length = OUT_LEN as u8;
+
+        Blake2xbReader {
+            params,
+            root: hash,
+            block: [0; OUT_LEN],
+            block_len: 0,
+            position: 0,
+            node: 0,
+        }
+    }
+}
+
+impl Default for Blake2xb {
+    fn default() -> Self {
+        Self::new(None, &[], &[], &[]).unwrap()
+    }
+}
+
+impl Update for Blake2xb {
+    fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            State::Deferred(input) => input.extend_from_slice(data),
+            State::Root(root) => root.update(data),
+        }
+    }
+}
+
+impl XofExpander for Blake2xb {
+    /// Fills `output` with an output of its length.
+    ///
+    /// # Panics
+    /// Panics if another output length was given to [`Blake2xb::new`].
+    fn finalize_expand(self, output: &mut [u8]) {
+        let output_len = u32::try_from(output.len())
+            .ok()
+            .filter(|&len| len != Self::UNKNOWN_OUTPUT_LEN)
+            .expect("BLAKE2xb output too long");
+        if let State::Root(_) = self.state {
+            assert_eq!(
+                self.params.xof_length, output_len,
+                "The output length of BLAKE2xb is not the size of the checksum."
+            );
+        }
+        self.finalize_root(output_len).read(output);
+    }
+}
+
+/// The reader of the output of [`Blake2xb`].
+#[derive(Clone)]
+pub struct Blake2xbReader {
+    params: Params,
+    root: [u8; OUT_LEN],
+    block: [u8; OUT_LEN],
+    block_len: usize,
+    position: usize,
+    node: u32,
+}
+
+impl XofReader for Blake2xbReader {
+    /// Reads the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics when reading past the output length given to [`Blake2xb`].
+    fn read(&mut self, mut buffer: &mut [u8]) {
+        while !buffer.is_empty() {
+            if self.position == self.block_len {
+                self.next_block();
+            }
+
+            let len = buffer.len().min(self.block_len - self.position);
+            buffer[..len].copy_from_slice(
+                &self.block[self.position..self.position + len],
+            );
+            self.position += len;
+            buffer = &mut buffer[len..];
+        }
+    }
+}
+
+impl Blake2xbReader {
+    fn next_block(&mut self) {
+        let xof_length = self.params.xof_length;
+        let digest_length = if xof_length == Blake2xb::UNKNOWN_OUTPUT_LEN {
+            OUT_LEN
+        } else {
+            let offset = self.node as u64 * OUT_LEN as u64;
+            assert!(offset < xof_length as u64, "BLAKE2xb output exhausted");
+            (xof_length as u64 - offset).min(OUT_LEN as u64) as usize
+        };
+
+        self.params.digest_length = digest_length as u8;
+        self.params.node_offset = self.node;
+        let mut instance = Blake2b::new(&self.params, &[]);
+        instance.update(&self.root);
+        instance.finalize(&mut self.block[..digest_length]);
+
+        self.block_len = digest_length;
+        self.position = 0;
+        self.node =
+            self.node.checked_add(1).expect("BLAKE2xb output exhausted");
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use crate::{Blake2xb, Expander, LtHash};
+
+type ElementDigest = [u8; 32];
+
+/// A LtHash checksum verifying that only inserted elements are removed, meant
+/// to replace a checksum in tests and debug builds.
+///
+/// Along with the checksum, it keeps the net count of each element (by its
+/// BLAKE2xb digest), so it uses memory proportional to the number of distinct
+/// elements. [`remove`](LtHash::remove) panics on an element which was not
+/// inserted, except in deltas: the values returned by
+/// [`zero`](LtHash::zero), on which transactions,
+/// [`ConcurrentLtHash`](crate::ConcurrentLtHash), … build their changes, or
+/// by [`into_delta`](Self::into_delta), whose counts may go negative.
+/// [`validate`](Self::validate) checks the counts of a union or difference,
+/// while [`try_remove`](Self::try_remove) and
+/// [`try_difference`](Self::try_difference) fail instead of panicking.
+///
+/// Two values are equal if their checksums are, whatever their counts.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{CheckedLtHash, LtHash, LtHash16};
+/// # use sha3::Shake128;
+/// let mut lthash = CheckedLtHash::<LtHash16<Shake128>>::default();
+/// lthash.insert("hello");
+/// assert!(lthash.try_remove("hello").is_ok());
+/// assert!(lthash.try_remove("hello").is_err());
+///
+/// let mut delta = lthash.zero();
+/// delta.remove("hello");
+/// assert!(lthash.union(&delta).validate().is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CheckedLtHash<L> {
+    lthash: L,
+    elements: HashMap<ElementDigest, i64>,
+    /// Whether the checksum was read from bytes holding elements which are
+    /// not counted.
+    untracked: bool,
+    /// Whether the counts may go negative.
+    delta: bool,
+}
+
+impl<L> CheckedLtHash<L> {
+    /// Returns the checksum.
+    #[inline(always)]
+    pub fn lthash(&self) -> &L {
+        &self.lthash
+    }
+
+    /// Returns the checksum, dropping the counts.
+    #[inline(always)]
+    pub fn into_inner(self) -> L {
+        self.lthash
+    }
+
+    /// Returns the net number of times `element` was inserted, negative if it
+    /// was removed more times.
+    pub fn count(&self, element: impl AsRef<[u8]>) -> i64 {
+        let digest = digest(element.as_ref());
+        self.elements.get(&digest).copied().unwrap_or(0)
+    }
+
+    /// Returns the net number of elements.
+    pub fn len(&self) -> i64 {
+        self.elements.values().sum()
+    }
+
+    /// Returns `true` if every element was removed as many times as inserted.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns `true` if the counts may go negative.
+    #[inline(always)]
+    pub fn is_delta(&self) -> bool {
+        self.delta
+    }
+
+    /// Lets the counts go negative, so that elements which were not inserted
+    /// can be removed.
+    pub fn into_delta(mut self) -> Self {
+        self.delta = true;
+        self
+    }
+
+    /// Checks that no element was removed more times than inserted, which
+    /// holds for the checksums but not necessarily for the deltas.
+    ///
+    /// The elements of a checksum read from bytes (by
+    /// [`with_bytes`](LtHash::with_bytes)) are unknown, so their removal
+    /// cannot be checked.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.untracked {
+            return Ok(());
+        }
+
+        match self.elements.values().min() {
+            Some(&count) if count < 0 => Err(format!(
+                "An element was removed {} more times than inserted.",
+                -count
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<L: LtHash> CheckedLtHash<L> {
+    /// Removes an element, failing without changing `self` if it is not
+    /// counted.
+    pub fn try_remove(
+        &mut self,
+        element: impl AsRef<[u8]>,
+    ) -> Result<(), String> {
+        let element = element.as_ref();
+        let digest = digest(element);
+        check_removal(&self.elements, digest, 1)?;
+        add_count(&mut self.elements, digest, -1);
+        self.lthash.remove(element);
+        Ok(())
+    }
+
+    /// Takes the difference of `self` and `rhs`, failing if an element would
+    /// be removed more times than inserted.
+    pub fn try_difference(&self, rhs: &Self) -> Result<Self, String> {
+        for (&digest, &count) in &rhs.elements {
+            check_removal(&self.elements, digest, count)?;
+        }
+
+        Ok(self.difference(rhs))
+    }
+}
+
+impl<L: LtHash> LtHash for CheckedLtHash<L> {
+    const BYTE_LEN: usize = L::BYTE_LEN;
+
+    /// Inserts an element to the checksum and counts it.
+    fn insert(&mut self, element: impl AsRef<[u8]>) {
+        let element = element.as_ref();
+        add_count(&mut self.elements, digest(element), 1);
+        self.lthash.insert(element);
+    }
+
+    /// Removes an element from the checksum and uncounts it.
+    ///
+    /// # Panics
+    /// Panics if the element was not inserted, unless `self` is a delta or
+    /// was read from bytes.
+    fn remove(&mut self, element: impl AsRef<[u8]>) {
+        let element = element.as_ref();
+        let digest = digest(element);
+        if !self.delta && !self.untracked {
+            if let Err(error) = check_removal(&self.elements, digest, 1) {
+                panic!("{error}");
+            }
+        }
+        add_count(&mut self.elements, digest, -1);
+        self.lthash.remove(element);
+    }
+
+    /// Provides the hex value as String of the checksum.
+    fn to_hex_string(&self) -> String {
+        self.lthash.to_hex_string()
+    }
+
+    /// Takes the union of `self` and `rhs`, adding their counts, which is a
+    /// delta if both are.
+    fn union(&self, rhs: &Self) -> Self {
+        let mut elements = self.elements.clone();
+        for (&digest, &count) in &rhs.elements {
+            add_count(&mut elements, digest, count);
+        }
+
+        Self {
+            lthash: self.lthash.union(&rhs.lthash),
+            elements,
+            untracked: self.untracked || rhs.untracked,
+            delta: self.delta && rhs.delta,
+        }
+    }
+
+    /// Takes the difference of `self` and `rhs`, subtracting their counts,
+    /// which is a delta if `self` is.
+    fn difference(&self, rhs: &Self) -> Self {
+        let mut elements = self.elements.clone();
+        for (&digest, &count) in &rhs.elements {
+            add_count(&mut elements, digest, -count);
+        }
+
+        Self {
+            lthash: self.lthash.difference(&rhs.lthash),
+            elements,
+            untracked: self.untracked || rhs.untracked,
+            delta: self.delta,
+        }
+    }
+
+    /// Clears the internal checksum and the counts.
+    fn reset(&mut self) {
+        self.lthash.reset();
+        self.elements.clear();
+        self.untracked = false;
+    }
+
+    /// Converts self into the inner list of bytes of the checksum.
+    fn into_bytes(self) -> Vec<u8> {
+        self.lthash.into_bytes()
+    }
+
+    /// Returns an empty delta, with no counts.
+    fn zero(&self) -> Self {
+        Self {
+            lthash: self.lthash.zero(),
+            elements: HashMap::new(),
+            untracked: false,
+            delta: true,
+        }
+    }
+
+    /// Reads the bytes of a checksum, whose elements cannot be recovered: the
+    /// counts start at zero and [`validate`](Self::validate) no longer checks
+    /// them, unless the checksum is empty.
+    fn with_bytes(&self, bytes: &[u8]) -> Result<Self, String> {
+        let lthash = self.lthash.with_bytes(bytes)?;
+        let untracked = !lthash.is_zero();
+        Ok(Self {
+            lthash,
+            elements: HashMap::new(),
+            untracked,
+            delta: false,
+        })
+    }
+}
+
+impl<L: PartialEq> PartialEq for CheckedLtHash<L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.lthash == other.lthash
+    }
+}
+
+crate::wrapper!(CheckedLtHash<L>);
+
+fn digest(element: &[u8]) -> ElementDigest {
+    let mut digest = [0; 32];
+    Blake2xb::default().expand(element, &mut digest);
+    digest
+}
+
+fn add_count(
+    elements: &mut HashMap<ElementDigest, i64>,
+    digest: ElementDigest,
+    count: i64,
+) {
+    let entry = elements.entry(digest).or_default();
+    *entry += count;
+    if *entry == 0 {
+        elements.remove(&digest);
+    }
+}
+
+fn check_removal(
+    elements: &HashMap<ElementDigest, i64>,
+    digest: ElementDigest,
+    count: i64,
+) -> Result<(), String> {
+    let present = elements.get(&digest).copied().unwrap_or(0);
+    if count > 0 && present < count {
+        return Err(format!(
+            "Removing an element {count} times while it was inserted {present} times."
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Run-length compression of the checksums, see `to_compressed_bytes`.
+//!
+//! The bytes returned by `into_bytes` are split in words (of 2, 8 and 4 bytes
+//! for `LtHash16`, `LtHash20` and `LtHash32`) and encoded as a sequence of
+//! runs: the number of zero words, the number of literal words (both as LEB128
+//! varints) and the literal words themselves, until all the words are
+//! encoded.
+//!
+//! Mostly zero checksums (empty or partially reset ones) shrink to a few
+//! bytes, while random-looking ones grow by a few bytes.
+
+/// Compresses the `bytes` of a checksum made of words of `word_len` bytes.
+pub(crate) fn compress(bytes: &[u8], word_len: usize) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut rest = bytes;
+
+    while !rest.is_empty() {
+        let zeros = rest
+            .chunks_exact(word_len)
+            .take_while(|word| is_zero(word))
+            .count();
+        rest = &rest[zeros * word_len..];
+
+        let literals = rest
+            .chunks_exact(word_len)
+            .take_while(|word| !is_zero(word))
+            .count();
+        let (literals, tail) = rest.split_at(literals * word_len);
+        rest = tail;
+
+        write_varint(&mut output, zeros);
+        write_varint(&mut output, literals.len() / word_len);
+        output.extend_from_slice(literals);
+    }
+
+    output
+}
+
+/// Decompresses a checksum of `len` bytes made of words of `word_len` bytes.
+pub(crate) fn decompress(
+    mut input: &[u8],
+    word_len: usize,
+    len: usize,
+) -> Result<Vec<u8>, String> {
+    let words = len / word_len;
+    let mut output = Vec::with_capacity(len);
+
+    while output.len() < len {
+        let zeros = read_varint(&mut input)?;
+        let literals = read_varint(&mut input)?;
+        if zeros + literals == 0 {
+            return Err(String::from("Empty run."));
+        }
+        if output.len() / word_len + zeros + literals > words {
+            return Err(String::from("Too many words."));
+        }
+
+        output.resize(output.len() + zeros * word_len, 0);
+        let literals = input
+            .get(..literals * word_len)
+            .ok_or_else(|| String::from("Truncated literal words."))?;
+        output.extend_from_slice(literals);
+        input = &input[literals.len()..];
+    }
+
+    if !input.is_empty() {
+        return Err(String::from("Trailing bytes."));
+    }
+
+    Ok(output)
+}
+
+fn is_zero(word: &[u8]) -> bool {
+    word.iter().all(|&byte| byte == 0)
+}
+
+fn write_varint(output: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        output.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<usize, String> {
+    let mut value = 0usize;
+
+    // the runs are shorter than 2^14 words
+    for shift in [0, 7] {
+        let (&byte, rest) = input
+            .split_first()
+            .ok_or_else(|| String::from("Truncated run length."))?;
+        *input = rest;
+        value |= usize::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(String::from("Run length too long."))
+}
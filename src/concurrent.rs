@@ -0,0 +1,204 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+    thread,
+};
+
+use crate::LtHash;
+
+/// A checksum shared by many threads or tasks.
+///
+/// The elements are hashed without holding any lock, into a temporary or a
+/// [`Local`] accumulator, and only the cheap addition to one of the shards is
+/// done under its lock. As the addition is commutative, the shards can be
+/// merged in any order: [`snapshot`](Self::snapshot) locks all of them to
+/// return the checksum of every element inserted so far.
+///
+/// The locks are never held across an `.await`, so the checksum can be shared
+/// by asynchronous tasks (e.g. in an `Arc`) without an asynchronous mutex.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{ConcurrentLtHash, LtHash32};
+/// # use sha3::Shake128;
+/// # use std::iter::FromIterator;
+/// let lthash = ConcurrentLtHash::new(LtHash32::<Shake128>::new());
+/// std::thread::scope(|scope| {
+///     for chunk in [["apple", "banana"], ["kiwi", "mango"]] {
+///         let lthash = &lthash;
+///         scope.spawn(move || {
+///             let mut local = lthash.local();
+///             local.extend(chunk);
+///         });
+///     }
+/// });
+/// assert_eq!(
+///     lthash.snapshot(),
+///     LtHash32::from_iter(["apple", "banana", "kiwi", "mango"])
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentLtHash<L> {
+    shards: Box<[Mutex<L>]>,
+    // an empty checksum configured like the initial one
+    empty: L,
+    next: AtomicUsize,
+}
+
+impl<L: LtHash> ConcurrentLtHash<L> {
+    /// Shares `lthash`, with as many shards as the available parallelism.
+    pub fn new(lthash: L) -> Self {
+        let shards = thread::available_parallelism().map_or(1, usize::from);
+        Self::with_shards(lthash, shards)
+    }
+
+    /// Shares `lthash`, with `shards` shards (at least one).
+    pub fn with_shards(lthash: L, shards: usize) -> Self {
+        let empty = lthash.zero();
+        let shards = std::iter::once(lthash)
+            .chain(std::iter::repeat_n(empty.clone(), shards.max(1) - 1))
+            .map(Mutex::new)
+            .collect();
+        Self {
+            shards,
+            empty,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of shards.
+    #[inline(always)]
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Inserts an element to the checksum.
+    ///
+    /// The element is hashed into a new checksum: prefer a [`Local`]
+    /// accumulator to insert many elements.
+    pub fn insert(&self, element: impl AsRef<[u8]>) {
+        let mut delta = self.empty.clone();
+        delta.insert(element);
+        self.union(&delta);
+    }
+
+    /// Removes an element from the checksum.
+    pub fn remove(&self, element: impl AsRef<[u8]>) {
+        let mut delta = self.empty.clone();
+        delta.remove(element);
+        self.union(&delta);
+    }
+
+    /// Adds `rhs` to the checksum.
+    pub fn union(&self, rhs: &L) {
+        let mut shard = self.shard();
+        *shard = shard.union(rhs);
+    }
+
+    /// Subtracts `rhs` from the checksum.
+    pub fn difference(&self, rhs: &L) {
+        let mut shard = self.shard();
+        *shard = shard.difference(rhs);
+    }
+
+    /// Returns an accumulator hashing the elements without any
+    /// synchronization, merged to the checksum when flushed or dropped.
+    pub fn local(&self) -> Local<'_, L> {
+        Local {
+            shared: self,
+            lthash: self.empty.clone(),
+            dirty: false,
+        }
+    }
+
+    /// Returns the checksum of all the changes merged so far.
+    ///
+    /// All the shards are locked at once, so the snapshot contains either all
+    /// or none of the changes of each merge.
+    pub fn snapshot(&self) -> L {
+        let guards: Vec<_> = self.shards.iter().map(lock).collect();
+        guards
+            .iter()
+            .fold(self.empty.clone(), |acc, shard| acc.union(shard))
+    }
+
+    /// Returns the checksum.
+    pub fn into_inner(self) -> L {
+        self.shards
+            .into_vec()
+            .into_iter()
+            .fold(self.empty, |acc, shard| {
+                acc.union(
+                    &shard.into_inner().unwrap_or_else(PoisonError::into_inner),
+                )
+            })
+    }
+
+    // Picks the shards in turn, skipping the locked ones if possible.
+    fn shard(&self) -> MutexGuard<'_, L> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let len = self.shards.len();
+        (0..len)
+            .find_map(|i| self.shards[(start + i) % len].try_lock().ok())
+            .unwrap_or_else(|| lock(&self.shards[start % len]))
+    }
+}
+
+// The shards are only updated by a single assignment, so a panic while one is
+// locked cannot leave it half updated.
+fn lock<L>(shard: &Mutex<L>) -> MutexGuard<'_, L> {
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An accumulator of the changes of a single thread or task to a
+/// [`ConcurrentLtHash`], returned by [`ConcurrentLtHash::local`].
+#[must_use = "the changes are merged when the accumulator is dropped"]
+#[derive(Debug)]
+pub struct Local<'a, L: LtHash> {
+    shared: &'a ConcurrentLtHash<L>,
+    lthash: L,
+    dirty: bool,
+}
+
+impl<L: LtHash> Local<'_, L> {
+    /// Inserts an element to the accumulator.
+    pub fn insert(&mut self, element: impl AsRef<[u8]>) {
+        self.lthash.insert(element);
+        self.dirty = true;
+    }
+
+    /// Removes an element from the accumulator.
+    pub fn remove(&mut self, element: impl AsRef<[u8]>) {
+        self.lthash.remove(element);
+        self.dirty = true;
+    }
+
+    /// Merges the accumulated changes to the shared checksum.
+    pub fn flush(&mut self) {
+        if self.dirty {
+            self.shared.union(&self.lthash);
+            self.lthash.reset();
+            self.dirty = false;
+        }
+    }
+}
+
+impl<A, L> Extend<A> for Local<'_, L>
+where
+    A: AsRef<[u8]>,
+    L: LtHash,
+{
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<L: LtHash> Drop for Local<'_, L> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
@@ -0,0 +1,144 @@
+use crate::LtHash;
+
+/// A LtHash checksum along with the net number of elements it contains.
+///
+/// The count is incremented by `insert` and decremented by `remove`, so a
+/// negative count reveals the removal of elements which were never inserted,
+/// and two checksums of sets of different sizes are never equal.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{CountedLtHash, LtHash, LtHash16};
+/// # use sha3::Shake128;
+/// let mut lthash = CountedLtHash::<LtHash16<Shake128>>::default();
+/// lthash.extend(["hello", "world"]);
+/// lthash.remove("hello");
+/// assert_eq!(lthash.count(), 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CountedLtHash<L> {
+    lthash: L,
+    count: i64,
+}
+
+impl<L> CountedLtHash<L> {
+    /// Pairs `lthash` with the number of elements it contains.
+    #[inline(always)]
+    pub fn from_parts(lthash: L, count: i64) -> Self {
+        Self { lthash, count }
+    }
+
+    /// Returns the checksum and the number of elements.
+    #[inline(always)]
+    pub fn into_parts(self) -> (L, i64) {
+        (self.lthash, self.count)
+    }
+
+    /// Returns the checksum.
+    #[inline(always)]
+    pub fn lthash(&self) -> &L {
+        &self.lthash
+    }
+
+    /// Returns the net number of elements, negative if more elements were
+    /// removed than inserted.
+    #[inline(always)]
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+}
+
+impl<L: LtHash> LtHash for CountedLtHash<L> {
+    /// The bytes of the checksum followed by the count.
+    const BYTE_LEN: usize = L::BYTE_LEN + 8;
+
+    /// Inserts an element to the checksum and increments the count.
+    fn insert(&mut self, element: impl AsRef<[u8]>) {
+        self.lthash.insert(element);
+        self.count += 1;
+    }
+
+    /// Removes an element from the checksum and decrements the count.
+    fn remove(&mut self, element: impl AsRef<[u8]>) {
+        self.lthash.remove(element);
+        self.count -= 1;
+    }
+
+    /// Provides the hex value as String of the checksum, without the count.
+    fn to_hex_string(&self) -> String {
+        self.lthash.to_hex_string()
+    }
+
+    /// Takes the union of `self` and `rhs`, adding their counts.
+    fn union(&self, rhs: &Self) -> Self {
+        Self {
+            lthash: self.lthash.union(&rhs.lthash),
+            count: self.count + rhs.count,
+        }
+    }
+
+    /// Takes the difference of `self` and `rhs`, subtracting their counts.
+    fn difference(&self, rhs: &Self) -> Self {
+        Self {
+            lthash: self.lthash.difference(&rhs.lthash),
+            count: self.count - rhs.count,
+        }
+    }
+
+    /// Clears the internal checksum and the count.
+    fn reset(&mut self) {
+        self.lthash.reset();
+        self.count = 0;
+    }
+
+    /// Converts self into the bytes of the checksum followed by the count, as
+    /// a little-endian `i64`.
+    fn into_bytes(self) -> Vec<u8> {
+        let mut bytes = self.lthash.into_bytes();
+        bytes.extend_from_slice(&self.count.to_le_bytes());
+        bytes
+    }
+
+    /// Returns an empty checksum, with a zero count.
+    fn zero(&self) -> Self {
+        Self {
+            lthash: self.lthash.zero(),
+            count: 0,
+        }
+    }
+
+    /// Reads the checksum and the count appended by
+    /// [`into_bytes`](Self::into_bytes).
+    fn with_bytes(&self, bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != Self::BYTE_LEN {
+            return Err(String::from("Wrong number of bytes."));
+        }
+
+        let (lthash, count) = bytes.split_at(L::BYTE_LEN);
+        Ok(Self {
+            lthash: self.lthash.with_bytes(lthash)?,
+            count: i64::from_le_bytes(count.try_into().unwrap()),
+        })
+    }
+}
+
+impl<L> TryFrom<&[u8]> for CountedLtHash<L>
+where
+    L: for<'a> TryFrom<&'a [u8], Error = String>,
+{
+    type Error = String;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < 8 {
+            return Err(String::from("Wrong number of bytes."));
+        }
+
+        let (lthash, count) = bytes.split_at(bytes.len() - 8);
+        Ok(Self {
+            lthash: L::try_from(lthash)?,
+            count: i64::from_le_bytes(count.try_into().unwrap()),
+        })
+    }
+}
+
+crate::wrapper!(CountedLtHash<L>);
@@ -0,0 +1,376 @@
+//! Object-safe LtHash checksums, to select the variant and the hash function at
+//! runtime (e.g. from a configuration file).
+//!
+//! [`DynLtHash`] is implemented for every [`LtHash`] and takes the elements as
+//! `&[u8]`, so that it can be used as `Box<dyn DynLtHash>`.
+//!
+//! # Examples
+//! ```
+//! # use lthash_rs::dynamic::{lthash_from_config, DynLtHash, Variant, Xof};
+//! let mut left = lthash_from_config(Variant::LtHash16, Xof::Blake2xb)?;
+//! left.dyn_insert(b"hello");
+//! let mut right = left.dyn_zero();
+//! right.dyn_insert(b"world");
+//!
+//! let union = left.dyn_union(right.as_ref())?;
+//! assert_eq!(union.byte_len(), 2048);
+//!
+//! let other = lthash_from_config(Variant::LtHash32, Xof::Blake2xb)?;
+//! assert!(left.dyn_union(other.as_ref()).is_err());
+//! # Ok::<(), String>(())
+//! ```
+
+use std::{any::Any, fmt, str::FromStr};
+
+use crate::{Blake2xb, Expander, LtHash, LtHash16, LtHash20, LtHash32, XofId};
+
+/// An object-safe [`LtHash`], implemented for all of them.
+///
+/// The methods are prefixed with `dyn_`, so that they don't clash with the
+/// ones of [`LtHash`] when both traits are in scope. The operations combining
+/// two checksums fail if they are not of the same type.
+pub trait DynLtHash: fmt::Debug + Send + Sync {
+    /// Inserts an element to the checksum.
+    fn dyn_insert(&mut self, element: &[u8]);
+    /// Removes an element from the checksum.
+    fn dyn_remove(&mut self, element: &[u8]);
+    /// Provides the hex value as String of the checksum.
+    fn dyn_to_hex_string(&self) -> String;
+    /// Takes the union of `self` and `rhs`.
+    fn dyn_union(
+        &self,
+        rhs: &dyn DynLtHash,
+    ) -> Result<Box<dyn DynLtHash>, String>;
+    /// Takes the difference of `self` and `rhs`.
+    fn dyn_difference(
+        &self,
+        rhs: &dyn DynLtHash,
+    ) -> Result<Box<dyn DynLtHash>, String>;
+    /// Clears the internal checksum.
+    fn dyn_reset(&mut self);
+    /// Returns the bytes of the checksum, like [`LtHash::into_bytes`].
+    fn to_bytes(&self) -> Vec<u8>;
+    /// Returns the number of bytes of the checksum.
+    fn byte_len(&self) -> usize;
+    /// Returns an empty checksum configured like `self`.
+    fn dyn_zero(&self) -> Box<dyn DynLtHash>;
+    /// Returns `true` if the checksum is the one of the empty set.
+    fn dyn_is_zero(&self) -> bool;
+    /// Returns a checksum configured like `self` holding `bytes`.
+    fn dyn_with_bytes(
+        &self,
+        bytes: &[u8],
+    ) -> Result<Box<dyn DynLtHash>, String>;
+    /// Clones the checksum.
+    fn clone_box(&self) -> Box<dyn DynLtHash>;
+    /// Returns `true` if `other` is the same checksum, of the same type.
+    fn dyn_eq(&self, other: &dyn DynLtHash) -> bool;
+    /// Returns the checksum as [`Any`], to downcast it to its type.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<L> DynLtHash for L
+where
+    L: LtHash + Send + Sync + 'static,
+{
+    fn dyn_insert(&mut self, element: &[u8]) {
+        LtHash::insert(self, element);
+    }
+
+    fn dyn_remove(&mut self, element: &[u8]) {
+        LtHash::remove(self, element);
+    }
+
+    fn dyn_to_hex_string(&self) -> String {
+        LtHash::to_hex_string(self)
+    }
+
+    fn dyn_union(
+        &self,
+        rhs: &dyn DynLtHash,
+    ) -> Result<Box<dyn DynLtHash>, String> {
+        Ok(Box::new(LtHash::union(self, downcast(rhs)?)))
+    }
+
+    fn dyn_difference(
+        &self,
+        rhs: &dyn DynLtHash,
+    ) -> Result<Box<dyn DynLtHash>, String> {
+        Ok(Box::new(LtHash::difference(self, downcast(rhs)?)))
+    }
+
+    fn dyn_reset(&mut self) {
+        LtHash::reset(self);
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.clone().into_bytes()
+    }
+
+    fn byte_len(&self) -> usize {
+        L::BYTE_LEN
+    }
+
+    fn dyn_zero(&self) -> Box<dyn DynLtHash> {
+        Box::new(LtHash::zero(self))
+    }
+
+    fn dyn_is_zero(&self) -> bool {
+        LtHash::is_zero(self)
+    }
+
+    fn dyn_with_bytes(
+        &self,
+        bytes: &[u8],
+    ) -> Result<Box<dyn DynLtHash>, String> {
+        Ok(Box::new(LtHash::with_bytes(self, bytes)?))
+    }
+
+    fn clone_box(&self) -> Box<dyn DynLtHash> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, other: &dyn DynLtHash) -> bool {
+        other.as_any().downcast_ref::<L>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn downcast<L: 'static>(rhs: &dyn DynLtHash) -> Result<&L, String> {
+    rhs.as_any()
+        .downcast_ref()
+        .ok_or_else(|| String::from("The checksums are of different types."))
+}
+
+impl Clone for Box<dyn DynLtHash> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl PartialEq for dyn DynLtHash {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+/// The variants of LtHash.
+///
+/// With the `cli` feature, it is a `clap::ValueEnum` whose values are the
+/// number of bits per chunk (`16`, `20` and `32`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Variant {
+    #[cfg_attr(feature = "cli", value(name = "16"))]
+    LtHash16,
+    #[cfg_attr(feature = "cli", value(name = "20"))]
+    LtHash20,
+    #[cfg_attr(feature = "cli", value(name = "32"))]
+    LtHash32,
+}
+
+impl Variant {
+    /// All the variants.
+    pub const ALL: [Variant; 3] =
+        [Variant::LtHash16, Variant::LtHash20, Variant::LtHash32];
+
+    /// Returns the name of the variant, e.g. `"LtHash16"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Variant::LtHash16 => "LtHash16",
+            Variant::LtHash20 => "LtHash20",
+            Variant::LtHash32 => "LtHash32",
+        }
+    }
+}
+
+/// The extendable output functions hashing the elements.
+///
+/// All but BLAKE2xb require a feature of the crate: `sha3` for SHAKE128 and
+/// SHAKE256, `blake3` for BLAKE3 and `k12` for KangarooTwelve.
+///
+/// With the `cli` feature, it is a `clap::ValueEnum` whose values are the
+/// identifiers returned by [`Xof::id`] of the enabled functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Xof {
+    Blake2xb,
+    Shake128,
+    Shake256,
+    #[cfg_attr(all(feature = "cli", not(feature = "blake3")), value(skip))]
+    Blake3,
+    #[cfg_attr(all(feature = "cli", not(feature = "k12")), value(skip))]
+    K12,
+}
+
+impl Xof {
+    /// All the extendable output functions.
+    pub const ALL: [Xof; 5] = [
+        Xof::Blake2xb,
+        Xof::Shake128,
+        Xof::Shake256,
+        Xof::Blake3,
+        Xof::K12,
+    ];
+
+    /// Returns the identifier of the function in an [`Algorithm`], e.g.
+    /// `"shake128"`.
+    pub const fn id(self) -> &'static str {
+        match self {
+            Xof::Blake2xb => "blake2xb",
+            Xof::Shake128 => "shake128",
+            Xof::Shake256 => "shake256",
+            Xof::Blake3 => "blake3",
+            Xof::K12 => "k12",
+        }
+    }
+
+    /// Returns the [`XofId`] of the function, `None` if the feature providing
+    /// it is disabled.
+    pub const fn xof_id(self) -> Option<u16> {
+        #[allow(unreachable_patterns)]
+        match self {
+            Xof::Blake2xb => Some(Blake2xb::XOF_ID),
+            #[cfg(feature = "sha3")]
+            Xof::Shake128 => Some(sha3::Shake128::XOF_ID),
+            #[cfg(feature = "sha3")]
+            Xof::Shake256 => Some(sha3::Shake256::XOF_ID),
+            #[cfg(feature = "blake3")]
+            Xof::Blake3 => Some(blake3::Hasher::XOF_ID),
+            #[cfg(feature = "k12")]
+            Xof::K12 => Some(k12::KangarooTwelve::XOF_ID),
+            _ => None,
+        }
+    }
+
+    /// Returns the function whose [`XofId`] is `id`.
+    pub(crate) fn from_xof_id(id: u16) -> Option<Xof> {
+        Xof::ALL.into_iter().find(|xof| xof.xof_id() == Some(id))
+    }
+}
+
+/// The algorithms, a variant of LtHash with a hash function, identified by
+/// stable names such as `"lthash16-shake128"` (e.g. to be put in manifests).
+///
+/// The identifier is the lowercase name of the variant followed by the one of
+/// the function, as returned by [`Xof::id`].
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{Algorithm, LtHash32Blake2xb};
+/// # use lthash_rs::dynamic::{Variant, Xof};
+/// let algorithm: Algorithm = "lthash32-shake256".parse()?;
+/// assert_eq!(algorithm, Algorithm::LtHash32(Xof::Shake256));
+/// assert_eq!(algorithm.variant(), Variant::LtHash32);
+/// assert_eq!(algorithm.to_string(), "lthash32-shake256");
+///
+/// let algorithm = LtHash32Blake2xb::algorithm().unwrap();
+/// assert_eq!(algorithm.to_string(), "lthash32-blake2xb");
+/// let lthash = algorithm.new_dyn()?;
+/// assert_eq!(lthash.byte_len(), 4096);
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    LtHash16(Xof),
+    LtHash20(Xof),
+    LtHash32(Xof),
+}
+
+impl Algorithm {
+    /// Returns the algorithm of `variant` hashing the elements with `xof`.
+    pub const fn new(variant: Variant, xof: Xof) -> Self {
+        match variant {
+            Variant::LtHash16 => Algorithm::LtHash16(xof),
+            Variant::LtHash20 => Algorithm::LtHash20(xof),
+            Variant::LtHash32 => Algorithm::LtHash32(xof),
+        }
+    }
+
+    /// Returns all the algorithms, including those whose hash function is
+    /// disabled.
+    pub fn all() -> impl Iterator<Item = Algorithm> {
+        Variant::ALL.into_iter().flat_map(|variant| {
+            Xof::ALL
+                .into_iter()
+                .map(move |xof| Algorithm::new(variant, xof))
+        })
+    }
+
+    /// Returns the variant of LtHash.
+    pub const fn variant(self) -> Variant {
+        match self {
+            Algorithm::LtHash16(_) => Variant::LtHash16,
+            Algorithm::LtHash20(_) => Variant::LtHash20,
+            Algorithm::LtHash32(_) => Variant::LtHash32,
+        }
+    }
+
+    /// Returns the hash function.
+    pub const fn xof(self) -> Xof {
+        match self {
+            Algorithm::LtHash16(xof)
+            | Algorithm::LtHash20(xof)
+            | Algorithm::LtHash32(xof) => xof,
+        }
+    }
+
+    /// Creates an empty checksum of the algorithm, like
+    /// [`lthash_from_config`].
+    pub fn new_dyn(self) -> Result<Box<dyn DynLtHash>, String> {
+        lthash_from_config(self.variant(), self.xof())
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let variant = self.variant().name().to_ascii_lowercase();
+        write!(f, "{}-{}", variant, self.xof().id())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Algorithm::all()
+            .find(|algorithm| algorithm.to_string() == s)
+            .ok_or_else(|| format!("Unknown algorithm {s:?}."))
+    }
+}
+
+/// Creates an empty checksum of `variant` hashing the elements with `xof`.
+///
+/// Fails if the feature providing `xof` is disabled.
+pub fn lthash_from_config(
+    variant: Variant,
+    xof: Xof,
+) -> Result<Box<dyn DynLtHash>, String> {
+    fn new<H>(variant: Variant) -> Box<dyn DynLtHash>
+    where
+        H: Expander + Default + Send + Sync + 'static,
+    {
+        match variant {
+            Variant::LtHash16 => Box::new(LtHash16::<H>::new()),
+            Variant::LtHash20 => Box::new(LtHash20::<H>::new()),
+            Variant::LtHash32 => Box::new(LtHash32::<H>::new()),
+        }
+    }
+
+    #[allow(unreachable_patterns)]
+    match xof {
+        Xof::Blake2xb => Ok(new::<Blake2xb>(variant)),
+        #[cfg(feature = "sha3")]
+        Xof::Shake128 => Ok(new::<sha3::Shake128>(variant)),
+        #[cfg(feature = "sha3")]
+        Xof::Shake256 => Ok(new::<sha3::Shake256>(variant)),
+        #[cfg(feature = "blake3")]
+        Xof::Blake3 => Ok(new::<blake3::Hasher>(variant)),
+        #[cfg(feature = "k12")]
+        Xof::K12 => Ok(new::<k12::KangarooTwelve<'static>>(variant)),
+        _ => Err(format!("The feature providing {xof:?} is disabled.")),
+    }
+}
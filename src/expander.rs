@@ -0,0 +1,182 @@
+//! Expansion of the elements into the bytes summed to the checksum.
+
+use digest::{Digest, ExtendableOutput, Update};
+
+use crate::{utils::wipe, Blake2xb};
+
+/// Expands an element into the bytes summed to the checksum (2048 bytes for
+/// `LtHash16`, 2688 for `LtHash20` and 4096 for `LtHash32`), which must look
+/// uniformly random.
+///
+/// It is implemented for the [`XofExpander`]s, which feed the element to a
+/// clone of themselves (all the extendable output functions and
+/// [`Blake2xb`]), and by [`CounterMode`], [`Precomputed`] and `AesCtr` (`aes`
+/// feature).
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{CounterMode, LtHash, LtHash16};
+/// # use sha3::Sha3_256;
+/// # use std::iter::FromIterator;
+/// let mut lthash = LtHash16::<CounterMode<Sha3_256>>::new();
+/// lthash.insert("hello");
+/// lthash.insert("world");
+/// assert_eq!(lthash, LtHash16::from_iter(["world", "hello"]));
+/// ```
+pub trait Expander: Clone {
+    /// Fills `output` with the expansion of `element`.
+    fn expand(&self, element: &[u8], output: &mut [u8]);
+}
+
+/// An [`Expander`] fed the element piece by piece, like an extendable output
+/// function, so that it can be streamed (`futures` feature).
+pub trait XofExpander: Update + Clone {
+    /// Fills `output` with the expansion of the element fed to `self`.
+    fn finalize_expand(self, output: &mut [u8]);
+}
+
+impl<H: XofExpander> Expander for H {
+    fn expand(&self, element: &[u8], output: &mut [u8]) {
+        let mut hasher = self.clone();
+        hasher.update(element);
+        hasher.finalize_expand(output);
+    }
+}
+
+impl<H: ExtendableOutput + Clone> XofExpander for H {
+    fn finalize_expand(self, output: &mut [u8]) {
+        self.finalize_xof_into(output);
+    }
+}
+
+/// Marks the [`Expander`]s whose output does not depend on its length: the
+/// first bytes of a long output are the whole of a shorter one.
+///
+/// It is implemented for SHAKE and cSHAKE (`sha3` feature), BLAKE3 (`blake3`
+/// feature), KangarooTwelve (`k12` feature), [`CounterMode`] and `AesCtr`, but
+/// not for [`Blake2xb`], whose output length is a parameter of the hash.
+pub trait PrefixStable {}
+
+#[cfg(feature = "sha3")]
+impl PrefixStable for sha3::Shake128 {}
+
+#[cfg(feature = "sha3")]
+impl PrefixStable for sha3::Shake256 {}
+
+#[cfg(feature = "sha3")]
+impl PrefixStable for sha3::CShake128 {}
+
+#[cfg(feature = "sha3")]
+impl PrefixStable for sha3::CShake256 {}
+
+#[cfg(feature = "blake3")]
+impl PrefixStable for blake3::Hasher {}
+
+#[cfg(feature = "k12")]
+impl PrefixStable for k12::KangarooTwelve<'_> {}
+
+/// Expands the elements with a fixed-output hash function in counter mode,
+/// like MGF1 (RFC 8017): the output is made of the digests of the element
+/// followed by a 32-bit big-endian counter starting at 0, the last one being
+/// truncated.
+///
+/// The digest can be fed beforehand, e.g. with a domain separation prefix.
+#[derive(Clone, Debug, Default)]
+pub struct CounterMode<D> {
+    digest: D,
+}
+
+impl<D> CounterMode<D> {
+    /// Expands the elements with clones of `digest`.
+    pub fn new(digest: D) -> Self {
+        Self { digest }
+    }
+}
+
+impl<D> PrefixStable for CounterMode<D> {}
+
+impl<D: Digest + Clone> Expander for CounterMode<D> {
+    fn expand(&self, element: &[u8], output: &mut [u8]) {
+        let mut hasher = self.digest.clone();
+        Digest::update(&mut hasher, element);
+
+        let blocks = output.chunks_mut(<D as Digest>::output_size());
+        for (counter, block) in (0u32..).zip(blocks) {
+            let mut block_hasher = hasher.clone();
+            Digest::update(&mut block_hasher, counter.to_be_bytes());
+            let mut digest = block_hasher.finalize();
+            block.copy_from_slice(&digest[..block.len()]);
+            wipe(&mut digest);
+        }
+    }
+}
+
+/// Takes the elements as their expansion, already computed (e.g. upstream,
+/// by whoever hashed them).
+///
+/// The elements of at most 64 bytes, such as a 32-byte digest computed
+/// upstream, are expanded with an unkeyed [`Blake2xb`].
+///
+/// # Panics
+/// Expanding an element longer than 64 bytes but not of the output length
+/// panics, as it is most likely an expansion for another checksum.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Precomputed;
+
+// the longest digests expanded by `Precomputed`, e.g. of SHA-512
+const MAX_DIGEST_LEN: usize = 64;
+
+impl Expander for Precomputed {
+    fn expand(&self, element: &[u8], output: &mut [u8]) {
+        if element.len() == output.len() {
+            output.copy_from_slice(element);
+        } else {
+            assert!(
+                element.len() <= MAX_DIGEST_LEN,
+                "The precomputed element has {} bytes instead of {}.",
+                element.len(),
+                output.len()
+            );
+            Blake2xb::default().expand(element, output);
+        }
+    }
+}
+
+/// Expands the elements with AES-256 in counter mode (`aes` feature), keyed
+/// with their 32-byte digest and starting from a zero counter block.
+///
+/// With hardware AES, this is faster than most extendable output functions
+/// for the long expansions of LtHash.
+#[cfg(feature = "aes")]
+#[derive(Clone, Debug, Default)]
+pub struct AesCtr<D> {
+    digest: D,
+}
+
+#[cfg(feature = "aes")]
+impl<D> AesCtr<D> {
+    /// Digests the elements with clones of `digest`.
+    pub fn new(digest: D) -> Self {
+        Self { digest }
+    }
+}
+
+#[cfg(feature = "aes")]
+impl<D> PrefixStable for AesCtr<D> {}
+
+#[cfg(feature = "aes")]
+impl<D> Expander for AesCtr<D>
+where
+    D: Digest<OutputSize = digest::consts::U32> + Clone,
+{
+    fn expand(&self, element: &[u8], output: &mut [u8]) {
+        use ctr::cipher::{KeyIvInit, StreamCipher};
+
+        let mut key = self.digest.clone().chain_update(element).finalize();
+        let mut cipher =
+            ctr::Ctr128BE::<aes::Aes256>::new(&key, &Default::default());
+        output.fill(0);
+        cipher.apply_keystream(output);
+        wipe(&mut key);
+    }
+}
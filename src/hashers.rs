@@ -0,0 +1,43 @@
+//! Aliases of [`LtHash16`](crate::LtHash16), [`LtHash20`](crate::LtHash20) and
+//! [`LtHash32`](crate::LtHash32) for the supported hash functions, the ones
+//! provided by other crates being available behind the feature of the same
+//! name.
+
+/// `LtHash16` using BLAKE2xb, following the algorithm of `LtHash<16, 1024>`
+/// in folly (unverified against folly).
+pub type LtHash16Blake2xb = crate::LtHash16<crate::Blake2xb>;
+/// `LtHash20` using BLAKE2xb, following the algorithm of `LtHash<20, 1008>`
+/// in folly (unverified against folly).
+pub type LtHash20Blake2xb = crate::LtHash20<crate::Blake2xb>;
+/// `LtHash32` using BLAKE2xb, following the algorithm of `LtHash<32, 1024>`
+/// in folly (unverified against folly).
+pub type LtHash32Blake2xb = crate::LtHash32<crate::Blake2xb>;
+
+/// `LtHash16` using SHAKE128 (`sha3` feature).
+#[cfg(feature = "sha3")]
+pub type LtHash16Shake128 = crate::LtHash16<sha3::Shake128>;
+/// `LtHash16` using SHAKE256 (`sha3` feature).
+#[cfg(feature = "sha3")]
+pub type LtHash16Shake256 = crate::LtHash16<sha3::Shake256>;
+/// `LtHash32` using SHAKE128 (`sha3` feature).
+#[cfg(feature = "sha3")]
+pub type LtHash32Shake128 = crate::LtHash32<sha3::Shake128>;
+/// `LtHash32` using SHAKE256 (`sha3` feature).
+#[cfg(feature = "sha3")]
+pub type LtHash32Shake256 = crate::LtHash32<sha3::Shake256>;
+
+/// `LtHash16` using the extendable output of BLAKE3 (`blake3` feature).
+#[cfg(feature = "blake3")]
+pub type LtHash16Blake3 = crate::LtHash16<blake3::Hasher>;
+/// `LtHash32` using the extendable output of BLAKE3 (`blake3` feature).
+#[cfg(feature = "blake3")]
+pub type LtHash32Blake3 = crate::LtHash32<blake3::Hasher>;
+
+/// `LtHash16` using KangarooTwelve with an empty customization string (`k12`
+/// feature).
+#[cfg(feature = "k12")]
+pub type LtHash16K12 = crate::LtHash16<k12::KangarooTwelve<'static>>;
+/// `LtHash32` using KangarooTwelve with an empty customization string (`k12`
+/// feature).
+#[cfg(feature = "k12")]
+pub type LtHash32K12 = crate::LtHash32<k12::KangarooTwelve<'static>>;
@@ -0,0 +1,377 @@
+//! Append-only journal of the operations applied to a checksum, to show how
+//! it was derived.
+//!
+//! Each step records the operation, the digest of its operand (the element
+//! inserted or removed, or the checksum added or subtracted) and the digest of
+//! the resulting checksum. Replaying the [`Log`] with the operands reproduces
+//! the checksum, reporting the first step diverging from the journal.
+//!
+//! # Examples
+//! ```
+//! # use lthash_rs::{journal::{Journal, Operand}, LtHash, LtHash16};
+//! # use sha3::{Sha3_256, Shake128};
+//! let mut journal =
+//!     Journal::<_, Sha3_256>::new(LtHash16::<Shake128>::new());
+//! journal.insert("hello");
+//! journal.insert("world");
+//! journal.remove("hello");
+//!
+//! let log = journal.log().to_bytes();
+//! let operands = ["hello", "world", "hello"].map(|element| {
+//!     Operand::Element(element.as_bytes())
+//! });
+//! let lthash = lthash_rs::journal::Log::<Sha3_256>::from_bytes(&log)
+//!     .unwrap()
+//!     .replay(LtHash16::new(), operands)
+//!     .unwrap();
+//! assert_eq!(&lthash, journal.lthash());
+//! ```
+
+use std::{fmt, marker::PhantomData};
+
+use digest::Digest;
+
+use crate::LtHash;
+
+const MAGIC: &[u8; 4] = b"LTHJ";
+const VERSION: u8 = 1;
+
+/// An operation applied to the checksum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Insert,
+    Remove,
+    Union,
+    Difference,
+}
+
+impl Operation {
+    fn from_u8(op: u8) -> Option<Self> {
+        match op {
+            0 => Some(Self::Insert),
+            1 => Some(Self::Remove),
+            2 => Some(Self::Union),
+            3 => Some(Self::Difference),
+            _ => None,
+        }
+    }
+}
+
+/// A step of the journal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The operation.
+    pub operation: Operation,
+    /// The digest of the element, or of the bytes of the checksum for
+    /// [`Union`](Operation::Union) and [`Difference`](Operation::Difference).
+    pub operand: Vec<u8>,
+    /// The digest of the bytes of the checksum after the operation.
+    pub checksum: Vec<u8>,
+}
+
+/// The operand of a step, given to [`Log::replay`].
+#[derive(Debug)]
+pub enum Operand<'a, L> {
+    /// The element inserted or removed.
+    Element(&'a [u8]),
+    /// The checksum added or subtracted.
+    LtHash(&'a L),
+}
+
+/// The first step of a replay diverging from the journal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Divergence {
+    /// The initial checksum is not the journaled one.
+    Initial,
+    /// The operand of the step (from 0) is missing, of the wrong kind or not
+    /// the journaled one.
+    Operand(usize),
+    /// The checksum after the step (from 0) is not the journaled one.
+    Checksum(usize),
+    /// There are more operands than steps.
+    TrailingOperands,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Initial => write!(f, "the initial checksum differs"),
+            Self::Operand(step) => {
+                write!(f, "the operand of step {step} differs")
+            }
+            Self::Checksum(step) => {
+                write!(f, "the checksum after step {step} differs")
+            }
+            Self::TrailingOperands => {
+                write!(f, "there are more operands than steps")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// The journal of the steps, hashed with `D`.
+pub struct Log<D> {
+    initial: Vec<u8>,
+    entries: Vec<Entry>,
+    digest: PhantomData<fn() -> D>,
+}
+
+impl<D> Clone for Log<D> {
+    fn clone(&self) -> Self {
+        Self {
+            initial: self.initial.clone(),
+            entries: self.entries.clone(),
+            digest: PhantomData,
+        }
+    }
+}
+
+impl<D> fmt::Debug for Log<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Log")
+            .field("initial", &self.initial)
+            .field("entries", &self.entries)
+            .finish()
+    }
+}
+
+impl<D> PartialEq for Log<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.initial == other.initial && self.entries == other.entries
+    }
+}
+
+impl<D> Eq for Log<D> {}
+
+impl<D: Digest> Log<D> {
+    /// Returns the digest of the bytes of the initial checksum.
+    #[inline(always)]
+    pub fn initial(&self) -> &[u8] {
+        &self.initial
+    }
+
+    /// Returns the steps.
+    #[inline(always)]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Serializes the journal.
+    ///
+    /// The format is the magic number `LTHJ`, the version (1), the length of
+    /// the digests, the digest of the initial checksum, the number of steps as
+    /// a little-endian `u64` and, for each step, the operation (0 to 3 for
+    /// insert, remove, union and difference) and the digests of the operand
+    /// and of the checksum.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let len = self.initial.len();
+        let mut bytes =
+            Vec::with_capacity(14 + len + self.entries.len() * (1 + 2 * len));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(len as u8);
+        bytes.extend_from_slice(&self.initial);
+        bytes.extend_from_slice(&(self.entries.len() as u64).to_le_bytes());
+        for entry in &self.entries {
+            bytes.push(entry.operation as u8);
+            bytes.extend_from_slice(&entry.operand);
+            bytes.extend_from_slice(&entry.checksum);
+        }
+        bytes
+    }
+
+    /// Deserializes a journal serialized by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let len = <D as Digest>::output_size();
+        let truncated = || String::from("Truncated journal.");
+
+        if bytes.len() < 6 || &bytes[..4] != MAGIC {
+            return Err(String::from("Wrong magic number."));
+        }
+        if bytes[4] != VERSION {
+            return Err(format!("Unsupported journal version {}.", bytes[4]));
+        }
+        if usize::from(bytes[5]) != len {
+            return Err(String::from("Wrong length of the digests."));
+        }
+
+        let mut rest = &bytes[6..];
+        let mut take = |n: usize| {
+            let (taken, tail) =
+                rest.split_at_checked(n).ok_or_else(truncated)?;
+            rest = tail;
+            Ok::<_, String>(taken)
+        };
+
+        let initial = take(len)?.to_vec();
+        let count = u64::from_le_bytes(take(8)?.try_into().unwrap());
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let operation = Operation::from_u8(take(1)?[0])
+                .ok_or_else(|| String::from("Unknown operation."))?;
+            entries.push(Entry {
+                operation,
+                operand: take(len)?.to_vec(),
+                checksum: take(len)?.to_vec(),
+            });
+        }
+
+        if !rest.is_empty() {
+            return Err(String::from("Trailing bytes."));
+        }
+
+        Ok(Self {
+            initial,
+            entries,
+            digest: PhantomData,
+        })
+    }
+
+    /// Replays the journal from `initial`, applying the `operands` in order
+    /// and checking every step, and returns the final checksum.
+    pub fn replay<'a, L, I>(
+        &self,
+        initial: L,
+        operands: I,
+    ) -> Result<L, Divergence>
+    where
+        L: LtHash + 'a,
+        I: IntoIterator<Item = Operand<'a, L>>,
+    {
+        if digest_lthash::<D, L>(&initial) != self.initial {
+            return Err(Divergence::Initial);
+        }
+
+        let mut lthash = initial;
+        let mut operands = operands.into_iter();
+        for (step, entry) in self.entries.iter().enumerate() {
+            let operand = operands.next().ok_or(Divergence::Operand(step))?;
+            let digest = match (entry.operation, operand) {
+                (Operation::Insert, Operand::Element(element)) => {
+                    lthash.insert(element);
+                    D::digest(element).to_vec()
+                }
+                (Operation::Remove, Operand::Element(element)) => {
+                    lthash.remove(element);
+                    D::digest(element).to_vec()
+                }
+                (Operation::Union, Operand::LtHash(rhs)) => {
+                    lthash = lthash.union(rhs);
+                    digest_lthash::<D, L>(rhs)
+                }
+                (Operation::Difference, Operand::LtHash(rhs)) => {
+                    lthash = lthash.difference(rhs);
+                    digest_lthash::<D, L>(rhs)
+                }
+                _ => return Err(Divergence::Operand(step)),
+            };
+
+            if digest != entry.operand {
+                return Err(Divergence::Operand(step));
+            }
+            if digest_lthash::<D, L>(&lthash) != entry.checksum {
+                return Err(Divergence::Checksum(step));
+            }
+        }
+
+        if operands.next().is_some() {
+            return Err(Divergence::TrailingOperands);
+        }
+
+        Ok(lthash)
+    }
+}
+
+/// A checksum journaling the operations applied to it.
+pub struct Journal<L, D> {
+    lthash: L,
+    log: Log<D>,
+}
+
+impl<L, D> Journal<L, D>
+where
+    L: LtHash,
+    D: Digest,
+{
+    /// Starts journaling the operations applied to `lthash`.
+    pub fn new(lthash: L) -> Self {
+        let log = Log {
+            initial: digest_lthash::<D, L>(&lthash),
+            entries: Vec::new(),
+            digest: PhantomData,
+        };
+
+        Self { lthash, log }
+    }
+
+    /// Returns the checksum.
+    #[inline(always)]
+    pub fn lthash(&self) -> &L {
+        &self.lthash
+    }
+
+    /// Returns the journal.
+    #[inline(always)]
+    pub fn log(&self) -> &Log<D> {
+        &self.log
+    }
+
+    /// Returns the checksum and the journal.
+    #[inline(always)]
+    pub fn into_parts(self) -> (L, Log<D>) {
+        (self.lthash, self.log)
+    }
+
+    /// Inserts an element to the checksum.
+    pub fn insert(&mut self, element: impl AsRef<[u8]>) {
+        let element = element.as_ref();
+        self.lthash.insert(element);
+        self.push(Operation::Insert, D::digest(element).to_vec());
+    }
+
+    /// Removes an element from the checksum.
+    pub fn remove(&mut self, element: impl AsRef<[u8]>) {
+        let element = element.as_ref();
+        self.lthash.remove(element);
+        self.push(Operation::Remove, D::digest(element).to_vec());
+    }
+
+    /// Adds `rhs` to the checksum.
+    pub fn union(&mut self, rhs: &L) {
+        self.lthash = self.lthash.union(rhs);
+        self.push(Operation::Union, digest_lthash::<D, L>(rhs));
+    }
+
+    /// Subtracts `rhs` from the checksum.
+    pub fn difference(&mut self, rhs: &L) {
+        self.lthash = self.lthash.difference(rhs);
+        self.push(Operation::Difference, digest_lthash::<D, L>(rhs));
+    }
+
+    fn push(&mut self, operation: Operation, operand: Vec<u8>) {
+        self.log.entries.push(Entry {
+            operation,
+            operand,
+            checksum: digest_lthash::<D, L>(&self.lthash),
+        });
+    }
+}
+
+impl<A, L, D> Extend<A> for Journal<L, D>
+where
+    A: AsRef<[u8]>,
+    L: LtHash,
+    D: Digest,
+{
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+fn digest_lthash<D: Digest, L: LtHash>(lthash: &L) -> Vec<u8> {
+    D::digest(lthash.clone().into_bytes()).to_vec()
+}
@@ -1,14 +1,51 @@
+mod blake2xb;
+mod checked;
+mod compression;
+mod concurrent;
+mod counted;
+pub mod dynamic;
+mod expander;
+mod hashers;
+pub mod journal;
 mod lthash16;
+mod lthash20;
 mod lthash32;
+mod lthash_ref;
+mod multi;
+mod pair;
+mod sequence;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "futures")]
+mod stream;
+mod transaction;
 mod utils;
+pub mod wire;
 
+pub use blake2xb::*;
+pub use checked::*;
+pub use concurrent::*;
+pub use counted::*;
+pub use dynamic::Algorithm;
+pub use expander::*;
+pub use hashers::*;
 pub use lthash16::*;
+pub use lthash20::*;
 pub use lthash32::*;
-
-use digest::ExtendableOutput;
+pub use lthash_ref::{Lanes, LtHashRef};
+pub use multi::*;
+pub use pair::*;
+pub use sequence::*;
+#[cfg(feature = "futures")]
+pub use stream::*;
+pub use transaction::*;
+pub use wire::XofId;
 
 /// Generic trait for LtHash, these functions will be implemented by all the instances of LtHash.
-pub trait LtHash {
+pub trait LtHash: Clone + PartialEq + core::fmt::Debug {
+    /// Number of bytes returned by [`into_bytes`](Self::into_bytes).
+    const BYTE_LEN: usize;
+
     /// Inserts an element to LtHash, actually it generates the hash (of size 2048 bytes) of the object and sums it to the checksum.
     fn insert(&mut self, element: impl AsRef<[u8]>);
     /// Removes an element to LtHash, actually it generates the hash (of size 2048 bytes) of the object and removes it from the checksum.
@@ -31,14 +68,127 @@ pub trait LtHash {
     fn reset(&mut self);
     /// Converts self into the inner list of bytes.
     fn into_bytes(self) -> Vec<u8>;
+    /// Returns an empty checksum configured (with its hasher) like `self`.
+    fn zero(&self) -> Self {
+        self.difference(self)
+    }
+    /// Returns `true` if the checksum is the one of the empty set.
+    fn is_zero(&self) -> bool {
+        *self == self.zero()
+    }
+    /// Returns a checksum configured like `self` holding the bytes returned by
+    /// [`into_bytes`](Self::into_bytes).
+    fn with_bytes(&self, bytes: &[u8]) -> Result<Self, String>;
+    /// Decodes the bytes returned by [`into_bytes`](Self::into_bytes).
+    fn from_bytes(bytes: &[u8]) -> Result<Self, String>
+    where
+        Self: Default,
+    {
+        Self::default().with_bytes(bytes)
+    }
 }
 
 macro_rules! common {
-    ($lthash:ty) => {
+    ($lthash:ty, $variant:ident, $bits:literal, $chunks:literal) => {
+        impl<H> $lthash {
+            /// The variant of LtHash, whose [`name`](dynamic::Variant::name)
+            /// is printed by `Debug`.
+            pub const VARIANT: dynamic::Variant = dynamic::Variant::$variant;
+        }
+
+        impl<H: XofId> $lthash {
+            /// Returns the [`Algorithm`] of the checksum, `None` if its hash
+            /// function is not one of [`dynamic::Xof`].
+            pub fn algorithm() -> Option<Algorithm> {
+                let xof = dynamic::Xof::from_xof_id(H::XOF_ID)?;
+                Some(Algorithm::new(Self::VARIANT, xof))
+            }
+
+            fn frame_kind() -> wire::FrameKind {
+                wire::FrameKind {
+                    bits: $bits,
+                    chunks: $chunks,
+                    xof_id: H::XOF_ID,
+                }
+            }
+
+            /// Encodes the checksum in the framed [wire format](wire), with
+            /// the number of elements if given.
+            pub fn encode(&self, elements: Option<u64>) -> Vec<u8> {
+                let checksum = utils::into_bytes(&self.checksum[..]);
+                wire::encode(Self::frame_kind(), elements, &checksum)
+            }
+
+            /// Decodes a checksum encoded by [`encode`](Self::encode),
+            /// returning it with the number of elements if present.
+            ///
+            /// The checksum hashes the elements with `H::default()`: use
+            /// [`decode_with_hasher`](Self::decode_with_hasher) for a keyed
+            /// or customized hasher.
+            ///
+            /// Fails if the frame is corrupt or holds another variant of
+            /// LtHash or a checksum computed with another hash function.
+            pub fn decode(frame: &[u8]) -> Result<(Self, Option<u64>), String>
+            where
+                H: Default,
+            {
+                Self::decode_with_hasher(frame, H::default())
+            }
+
+            /// Decodes a checksum encoded by [`encode`](Self::encode), which
+            /// hashes the elements with clones of `hasher`.
+            pub fn decode_with_hasher(
+                frame: &[u8],
+                hasher: H,
+            ) -> Result<(Self, Option<u64>), String> {
+                let (checksum, elements) =
+                    wire::decode(Self::frame_kind(), frame)?;
+                Ok((Self::read_bytes(checksum, hasher)?, elements))
+            }
+        }
+
+        impl<H> $lthash {
+            /// Returns the bytes of [`into_bytes`](LtHash::into_bytes)
+            /// run-length compressed, which is worth it for mostly zero
+            /// checksums only.
+            pub fn to_compressed_bytes(&self) -> Vec<u8> {
+                let word_len = std::mem::size_of_val(&self.checksum[0]);
+                let bytes = utils::into_bytes(&self.checksum[..]);
+                compression::compress(&bytes, word_len)
+            }
+
+            /// Decompresses a checksum returned by
+            /// [`to_compressed_bytes`](Self::to_compressed_bytes), which
+            /// hashes the elements with `H::default()`: use
+            /// [`from_compressed_bytes_with`](Self::from_compressed_bytes_with)
+            /// for a keyed or customized hasher.
+            pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, String>
+            where
+                H: Default,
+            {
+                Self::from_compressed_bytes_with(bytes, H::default())
+            }
+
+            /// Decompresses a checksum returned by
+            /// [`to_compressed_bytes`](Self::to_compressed_bytes), which
+            /// hashes the elements with clones of `hasher`.
+            pub fn from_compressed_bytes_with(
+                bytes: &[u8],
+                hasher: H,
+            ) -> Result<Self, String> {
+                // the layout of the checksum does not depend on the hasher
+                let layout = $variant::with_hasher(());
+                let word_len = std::mem::size_of_val(&layout.checksum[0]);
+                let len = word_len * layout.checksum.len();
+                let bytes = compression::decompress(bytes, word_len, len)?;
+                Self::read_bytes(&bytes, hasher)
+            }
+        }
+
         impl<A, H> Extend<A> for $lthash
         where
             A: AsRef<[u8]>,
-            H: ExtendableOutput + Default,
+            H: Expander,
         {
             fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
                 for item in iter {
@@ -47,29 +197,97 @@ macro_rules! common {
             }
         }
 
+        impl<H> $lthash {
+            /// Replaces the checksum with the one of `other` if `choice` is
+            /// set, in constant time. The hasher is left untouched.
+            pub fn conditional_assign(
+                &mut self,
+                other: &Self,
+                choice: subtle::Choice,
+            ) {
+                use subtle::ConditionallySelectable;
+                for (x, y) in self.checksum.iter_mut().zip(&other.checksum[..])
+                {
+                    x.conditional_assign(y, choice);
+                }
+            }
+
+            /// Returns a checksum equal to `b` if `choice` is set and to `a`
+            /// otherwise, in constant time, with the hasher of `a`.
+            pub fn conditional_select(
+                a: &Self,
+                b: &Self,
+                choice: subtle::Choice,
+            ) -> Self
+            where
+                H: Clone,
+            {
+                let mut selected = a.clone();
+                selected.conditional_assign(b, choice);
+                selected
+            }
+
+            /// Swaps the checksums of `a` and `b` if `choice` is set, in
+            /// constant time. The hashers are left untouched.
+            pub fn conditional_swap(
+                a: &mut Self,
+                b: &mut Self,
+                choice: subtle::Choice,
+            ) {
+                use subtle::ConditionallySelectable;
+                for (x, y) in a.checksum.iter_mut().zip(&mut b.checksum[..]) {
+                    ConditionallySelectable::conditional_swap(x, y, choice);
+                }
+            }
+        }
+
+        /// Compares the checksums in constant time.
+        impl<H> subtle::ConstantTimeEq for $lthash {
+            fn ct_eq(&self, other: &Self) -> subtle::Choice {
+                self.checksum[..].ct_eq(&other.checksum[..])
+            }
+        }
+
         impl<H> PartialEq for $lthash {
             fn eq(&self, other: &Self) -> bool {
-                subtle::ConstantTimeEq::ct_eq(
-                    &self.checksum[..],
-                    &other.checksum[..],
-                )
-                .into()
+                subtle::ConstantTimeEq::ct_eq(self, other).into()
             }
         }
 
+        impl<H> Eq for $lthash {}
+
+        /// Zeroes the checksum.
+        #[cfg(feature = "zeroize")]
+        impl<H> zeroize::Zeroize for $lthash {
+            fn zeroize(&mut self) {
+                self.checksum.zeroize();
+            }
+        }
+
+        /// Zeroes the checksum when dropped.
+        #[cfg(feature = "zeroize")]
+        impl<H> Drop for $lthash {
+            fn drop(&mut self) {
+                zeroize::Zeroize::zeroize(self);
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl<H> zeroize::ZeroizeOnDrop for $lthash {}
+
         impl<H> core::fmt::Debug for $lthash {
             fn fmt(
                 &self,
                 f: &mut core::fmt::Formatter<'_>,
             ) -> core::fmt::Result {
-                write!(f, "{} {:?}", self.name(), &self.checksum)
+                write!(f, "{} {:?}", Self::VARIANT.name(), &self.checksum)
             }
         }
 
         impl<A, H> FromIterator<A> for $lthash
         where
             A: AsRef<[u8]>,
-            H: ExtendableOutput + Default,
+            H: Expander + Default,
         {
             fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
                 let mut this = Self::default();
@@ -80,7 +298,7 @@ macro_rules! common {
 
         impl<'a, H> std::ops::BitOr for &'a $lthash
         where
-            H: ExtendableOutput + Default,
+            H: Expander,
         {
             type Output = $lthash;
 
@@ -91,7 +309,7 @@ macro_rules! common {
 
         impl<H> std::ops::BitOr for $lthash
         where
-            H: ExtendableOutput + Default,
+            H: Expander,
         {
             type Output = Self;
 
@@ -102,7 +320,7 @@ macro_rules! common {
 
         impl<'a, H> std::ops::Sub for &'a $lthash
         where
-            H: ExtendableOutput + Default,
+            H: Expander,
         {
             type Output = $lthash;
 
@@ -113,7 +331,7 @@ macro_rules! common {
 
         impl<H> std::ops::Sub for $lthash
         where
-            H: ExtendableOutput + Default,
+            H: Expander,
         {
             type Output = Self;
 
@@ -124,5 +342,68 @@ macro_rules! common {
     };
 }
 
-common!(LtHash16<H>);
-common!(LtHash32<H>);
+/// Implements the traits of the LtHash types for a wrapper of a `L: LtHash`.
+macro_rules! wrapper {
+    ($wrapper:ty) => {
+        impl<A, L> Extend<A> for $wrapper
+        where
+            A: AsRef<[u8]>,
+            L: $crate::LtHash,
+        {
+            fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+                for item in iter {
+                    self.insert(item);
+                }
+            }
+        }
+
+        impl<A, L> FromIterator<A> for $wrapper
+        where
+            A: AsRef<[u8]>,
+            L: $crate::LtHash + Default,
+        {
+            fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+                let mut this = Self::default();
+                this.extend(iter);
+                this
+            }
+        }
+
+        impl<L: $crate::LtHash> std::ops::BitOr for &$wrapper {
+            type Output = $wrapper;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                self.union(rhs)
+            }
+        }
+
+        impl<L: $crate::LtHash> std::ops::BitOr for $wrapper {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                self.union(&rhs)
+            }
+        }
+
+        impl<L: $crate::LtHash> std::ops::Sub for &$wrapper {
+            type Output = $wrapper;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.difference(rhs)
+            }
+        }
+
+        impl<L: $crate::LtHash> std::ops::Sub for $wrapper {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.difference(&rhs)
+            }
+        }
+    };
+}
+pub(crate) use wrapper;
+
+common!(LtHash16<H>, LtHash16, 16, 1024);
+common!(LtHash20<H>, LtHash20, 20, 1008);
+common!(LtHash32<H>, LtHash32, 32, 1024);
@@ -1,69 +1,155 @@
-use std::marker::PhantomData;
-
-use digest::ExtendableOutput;
+#[cfg(feature = "futures")]
+use crate::XofExpander;
+use subtle::Choice;
 
 use crate::{
-    utils::{into_bytes, read_u16, HexDisplayRef16},
-    LtHash,
+    utils::{into_bytes, mask, read_u16, wipe, zeroed, HexDisplayRef16},
+    Expander, Lanes, LtHash,
 };
 
 /// A LtHash checksum with 16 bits per chunk and 1024 chunks.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct LtHash16<H> {
-    pub(crate) checksum: [u16; 1024],
-    hasher: PhantomData<H>,
+    pub(crate) checksum: Box<[u16; 1024]>,
+    hasher: H,
 }
 
 // Ensure we don't accidentally remove Send/Sync, since LtHash16 should be Send/Sync.
 static_assertions::assert_impl_all!(LtHash16<()>: Send, Sync, Unpin);
 
 impl<H> LtHash16<H> {
-    pub(crate) const fn name(&self) -> &'static str {
-        "LtHash16"
+    /// Creates an empty checksum hashing the objects with clones of `hasher`,
+    /// which can be configured (with a key, a customization string, …)
+    /// beforehand.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lthash_rs::{LtHash, LtHash16};
+    /// # use sha3::{CShake128, CShake128Core};
+    /// let hasher = CShake128::from_core(CShake128Core::new(b"my-dataset"));
+    /// let mut lthash = LtHash16::with_hasher(hasher);
+    /// lthash.insert("hello");
+    /// ```
+    #[inline(always)]
+    pub fn with_hasher(hasher: H) -> Self {
+        Self {
+            checksum: zeroed(),
+            hasher,
+        }
+    }
+
+    /// Returns the hasher used to hash the objects.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    pub(crate) fn read_bytes(bytes: &[u8], hasher: H) -> Result<Self, String> {
+        if bytes.len() != 2048 {
+            return Err(String::from("Wrong number of bytes."));
+        }
+
+        let mut checksum: Box<[u16; 1024]> = zeroed();
+
+        for (checksum, bytes) in checksum.iter_mut().zip(bytes.chunks_exact(2))
+        {
+            *checksum =
+                u16::from_le_bytes(bytes.try_into().map_err(|_| {
+                    String::from("Error converting bytes to u16.")
+                })?);
+        }
+
+        Ok(Self { checksum, hasher })
     }
 }
 
 impl<H> LtHash16<H>
 where
-    H: ExtendableOutput + Default,
+    H: Expander + Default,
 {
     #[inline(always)]
     pub fn new() -> Self {
         Self::default()
     }
+}
 
-    fn hash_object(&mut self, object: impl AsRef<[u8]>) -> [u8; 2048] {
+// the elements streamed by `stream` are fed to the hasher piece by piece
+#[cfg(feature = "futures")]
+impl<H> LtHash16<H>
+where
+    H: XofExpander,
+{
+    fn finalize(hasher: H) -> [u8; 2048] {
         let mut output = [0u8; 2048];
-        H::digest_xof(object, output.as_mut());
+        hasher.finalize_expand(output.as_mut());
         output
     }
 
-    #[inline(always)]
-    fn display_hex_ref(&self) -> HexDisplayRef16<'_> {
-        HexDisplayRef16(&self.checksum[..])
+    /// Sums the output of `hasher`, fed with an element, to the checksum.
+    pub(crate) fn insert_hashed(&mut self, hasher: H) {
+        let mut hashed = Self::finalize(hasher);
+        self.add_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Subtracts the output of `hasher`, fed with an element, from the
+    /// checksum.
+    pub(crate) fn remove_hashed(&mut self, hasher: H) {
+        let mut hashed = Self::finalize(hasher);
+        self.sub_hashed(&hashed);
+        wipe(&mut hashed);
     }
 }
 
-impl<H> Default for LtHash16<H>
-where
-    H: ExtendableOutput + Default,
-{
-    #[inline(always)]
-    fn default() -> Self {
-        Self {
-            checksum: [0; 1024],
-            hasher: Default::default(),
+impl<H: Expander> LtHash16<H> {
+    fn expand(&self, element: impl AsRef<[u8]>) -> [u8; 2048] {
+        let mut output = [0u8; 2048];
+        self.hasher.expand(element.as_ref(), &mut output);
+        output
+    }
+
+    /// Inserts an element to the checksum if `choice` is set, in constant
+    /// time: the element is hashed either way, and the output masked.
+    pub fn conditional_insert(
+        &mut self,
+        element: impl AsRef<[u8]>,
+        choice: Choice,
+    ) {
+        let mut hashed = self.expand(element);
+        mask(&mut hashed, choice);
+        self.add_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Removes an element from the checksum if `choice` is set, in constant
+    /// time: the element is hashed either way, and the output masked.
+    pub fn conditional_remove(
+        &mut self,
+        element: impl AsRef<[u8]>,
+        choice: Choice,
+    ) {
+        let mut hashed = self.expand(element);
+        mask(&mut hashed, choice);
+        self.sub_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Adds `weight` times the hash of an element to the checksum, lane-wise
+    /// and wrapping: the same as inserting the element `weight` times, or
+    /// removing it `-weight` times if `weight` is negative.
+    pub fn insert_weighted(&mut self, element: impl AsRef<[u8]>, weight: i64) {
+        let mut hashed = self.expand(element);
+        let weight = weight as u16;
+        let mut i = 0;
+        while i < 2048 {
+            let yi = read_u16(&hashed[i..i + 2]).wrapping_mul(weight);
+            self.checksum[i / 2] = self.checksum[i / 2].wrapping_add(yi);
+            i += 2;
         }
+        wipe(&mut hashed);
     }
-}
 
-impl<H> LtHash for LtHash16<H>
-where
-    H: ExtendableOutput + Default,
-{
-    /// Inserts an element to LtHash, actually it generates the hash (of size 2048 bytes) of the object and sums it to the checksum.
-    fn insert(&mut self, element: impl AsRef<[u8]>) {
-        let hashed = self.hash_object(element);
+    pub(crate) fn add_hashed(&mut self, hashed: &[u8; 2048]) {
         let mut i = 0;
         while i < 2048 {
             let xi = &self.checksum[i / 2];
@@ -75,9 +161,7 @@ where
         }
     }
 
-    /// Removes an element to LtHash, actually it generates the hash (of size 2048 bytes) of the object and removes it from the checksum.
-    fn remove(&mut self, element: impl AsRef<[u8]>) {
-        let hashed = self.hash_object(element);
+    pub(crate) fn sub_hashed(&mut self, hashed: &[u8; 2048]) {
         let mut i = 0;
         while i < 2048 {
             let xi = &self.checksum[i / 2];
@@ -89,6 +173,39 @@ where
         }
     }
 
+    #[inline(always)]
+    fn display_hex_ref(&self) -> HexDisplayRef16<'_> {
+        HexDisplayRef16(&self.checksum[..])
+    }
+}
+
+impl<H> Default for LtHash16<H>
+where
+    H: Expander + Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::with_hasher(H::default())
+    }
+}
+
+impl<H: Expander> LtHash for LtHash16<H> {
+    const BYTE_LEN: usize = 2048;
+
+    /// Inserts an element to LtHash, actually it generates the hash (of size 2048 bytes) of the object and sums it to the checksum.
+    fn insert(&mut self, element: impl AsRef<[u8]>) {
+        let mut hashed = self.expand(element);
+        self.add_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Removes an element to LtHash, actually it generates the hash (of size 2048 bytes) of the object and removes it from the checksum.
+    fn remove(&mut self, element: impl AsRef<[u8]>) {
+        let mut hashed = self.expand(element);
+        self.sub_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
     /// Provides the hex value as String of the checksum.
     fn to_hex_string(&self) -> String {
         self.display_hex_ref().to_string()
@@ -115,7 +232,7 @@ where
     /// assert_eq!(left.union(&right), LtHash16::<Shake256>::from_iter(&["hello", "world"]));
     /// ```
     fn union(&self, rhs: &Self) -> Self {
-        let mut checksum = [0; 1024];
+        let mut checksum: Box<[u16; 1024]> = zeroed();
 
         for (checksum, (&lhs, &rhs)) in checksum
             .iter_mut()
@@ -126,7 +243,7 @@ where
 
         Self {
             checksum,
-            hasher: PhantomData,
+            hasher: self.hasher.clone(),
         }
     }
 
@@ -151,7 +268,7 @@ where
     /// assert_eq!(left.difference(&right), LtHash16::from_iter(&["world"]));
     /// ```
     fn difference(&self, rhs: &Self) -> Self {
-        let mut checksum = [0; 1024];
+        let mut checksum: Box<[u16; 1024]> = zeroed();
 
         for (checksum, (&lhs, &rhs)) in checksum
             .iter_mut()
@@ -162,7 +279,7 @@ where
 
         Self {
             checksum,
-            hasher: PhantomData,
+            hasher: self.hasher.clone(),
         }
     }
 
@@ -173,31 +290,56 @@ where
 
     /// Converts self into the inner list of bytes
     fn into_bytes(self) -> Vec<u8> {
-        into_bytes(self.checksum)
+        into_bytes(&self.checksum[..])
+    }
+
+    /// Returns an empty checksum with the hasher of `self`.
+    fn zero(&self) -> Self {
+        Self::with_hasher(self.hasher.clone())
+    }
+
+    fn with_bytes(&self, bytes: &[u8]) -> Result<Self, String> {
+        Self::read_bytes(bytes, self.hasher.clone())
     }
 }
 
-impl<H> TryFrom<&[u8]> for LtHash16<H> {
+/// Reads the bytes returned by [`into_bytes`](LtHash::into_bytes), hashing the
+/// elements with `H::default()`: the key or customization of the hasher the
+/// checksum was computed with is lost, so use [`LtHash::with_bytes`] to keep
+/// hashing with it.
+impl<H: Default> TryFrom<&[u8]> for LtHash16<H> {
     type Error = String;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::read_bytes(bytes, H::default())
+    }
+}
+
+impl<H> crate::lthash_ref::sealed::Sealed for LtHash16<H> {}
+
+impl<H: Expander> Lanes for LtHash16<H> {
+    const LANE_BITS: u32 = 16;
+    const LANE_COUNT: usize = 1024;
+
+    fn check_bytes(bytes: &[u8]) -> Result<(), String> {
         if bytes.len() != 2048 {
             return Err(String::from("Wrong number of bytes."));
         }
 
-        let mut checksum = [0; 1024];
+        Ok(())
+    }
 
-        for (checksum, bytes) in checksum.iter_mut().zip(bytes.chunks_exact(2))
-        {
-            *checksum =
-                u16::from_le_bytes(bytes.try_into().map_err(|_| {
-                    String::from("Error converting bytes to u16.")
-                })?);
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        for (x, y) in self.checksum.iter_mut().zip(bytes.chunks_exact(2)) {
+            let y = u16::from_le_bytes(y.try_into().unwrap());
+            *x = x.wrapping_add(y);
         }
+    }
 
-        Ok(Self {
-            checksum,
-            hasher: PhantomData,
-        })
+    fn sub_from_bytes(&mut self, bytes: &[u8]) {
+        for (x, y) in self.checksum.iter_mut().zip(bytes.chunks_exact(2)) {
+            let y = u16::from_le_bytes(y.try_into().unwrap());
+            *x = y.wrapping_sub(*x);
+        }
     }
 }
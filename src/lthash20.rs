@@ -0,0 +1,378 @@
+#[cfg(feature = "futures")]
+use crate::XofExpander;
+use subtle::Choice;
+
+use crate::{
+    utils::{into_bytes, mask, wipe, zeroed, HexDisplayRef64},
+    Expander, Lanes, LtHash,
+};
+
+/// Bits of every 64-bit word holding data, the others are padding bits.
+///
+/// Each word holds three chunks of 20 bits, each one followed by a padding bit
+/// absorbing its carry (the last one by two):
+/// `00 11111111111111111111 0 11111111111111111111 0 11111111111111111111`.
+const DATA_MASK: u64 = 0x3fff_fdff_ffef_ffff;
+
+/// Bits of a chunk, once shifted to the lowest bits.
+const CHUNK_MASK: u64 = 0xf_ffff;
+
+/// A LtHash checksum with 20 bits per chunk and 1008 chunks.
+///
+/// The chunks are packed three by three into 64-bit little-endian words, with
+/// the layout of `LtHash<20, 1008>` in folly.
+#[derive(Clone)]
+pub struct LtHash20<H> {
+    pub(crate) checksum: Box<[u64; 336]>,
+    hasher: H,
+}
+
+// Ensure we don't accidentally remove Send/Sync, since LtHash20 should be Send/Sync.
+static_assertions::assert_impl_all!(LtHash20<()>: Send, Sync, Unpin);
+
+impl<H> LtHash20<H> {
+    /// Creates an empty checksum hashing the objects with clones of `hasher`,
+    /// which can be configured (with a key, a customization string, …)
+    /// beforehand.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lthash_rs::{LtHash, LtHash20};
+    /// # use sha3::{CShake128, CShake128Core};
+    /// let hasher = CShake128::from_core(CShake128Core::new(b"my-dataset"));
+    /// let mut lthash = LtHash20::with_hasher(hasher);
+    /// lthash.insert("hello");
+    /// ```
+    #[inline(always)]
+    pub fn with_hasher(hasher: H) -> Self {
+        Self {
+            checksum: zeroed(),
+            hasher,
+        }
+    }
+
+    /// Returns the hasher used to hash the objects.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    pub(crate) fn read_bytes(bytes: &[u8], hasher: H) -> Result<Self, String> {
+        if bytes.len() != 2688 {
+            return Err(String::from("Wrong number of bytes."));
+        }
+
+        let mut checksum: Box<[u64; 336]> = zeroed();
+
+        for (checksum, bytes) in checksum.iter_mut().zip(bytes.chunks_exact(8))
+        {
+            *checksum =
+                u64::from_le_bytes(bytes.try_into().map_err(|_| {
+                    String::from("Error converting bytes to u64.")
+                })?);
+
+            if *checksum & !DATA_MASK != 0 {
+                return Err(String::from("Padding bits are not zero."));
+            }
+        }
+
+        Ok(Self { checksum, hasher })
+    }
+}
+
+impl<H> LtHash20<H>
+where
+    H: Expander + Default,
+{
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// the elements streamed by `stream` are fed to the hasher piece by piece
+#[cfg(feature = "futures")]
+impl<H> LtHash20<H>
+where
+    H: XofExpander,
+{
+    fn finalize(hasher: H) -> [u8; 2688] {
+        let mut output = [0u8; 2688];
+        hasher.finalize_expand(output.as_mut());
+        output
+    }
+
+    /// Sums the output of `hasher`, fed with an element, to the checksum.
+    pub(crate) fn insert_hashed(&mut self, hasher: H) {
+        let mut hashed = Self::finalize(hasher);
+        self.add_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Subtracts the output of `hasher`, fed with an element, from the
+    /// checksum.
+    pub(crate) fn remove_hashed(&mut self, hasher: H) {
+        let mut hashed = Self::finalize(hasher);
+        self.sub_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+}
+
+impl<H: Expander> LtHash20<H> {
+    fn expand(&self, element: impl AsRef<[u8]>) -> [u8; 2688] {
+        let mut output = [0u8; 2688];
+        self.hasher.expand(element.as_ref(), &mut output);
+        output
+    }
+
+    /// Inserts an element to the checksum if `choice` is set, in constant
+    /// time: the element is hashed either way, and the output masked.
+    pub fn conditional_insert(
+        &mut self,
+        element: impl AsRef<[u8]>,
+        choice: Choice,
+    ) {
+        let mut hashed = self.expand(element);
+        mask(&mut hashed, choice);
+        self.add_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Removes an element from the checksum if `choice` is set, in constant
+    /// time: the element is hashed either way, and the output masked.
+    pub fn conditional_remove(
+        &mut self,
+        element: impl AsRef<[u8]>,
+        choice: Choice,
+    ) {
+        let mut hashed = self.expand(element);
+        mask(&mut hashed, choice);
+        self.sub_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Adds `weight` times the hash of an element to the checksum, lane-wise
+    /// and wrapping: the same as inserting the element `weight` times, or
+    /// removing it `-weight` times if `weight` is negative.
+    pub fn insert_weighted(&mut self, element: impl AsRef<[u8]>, weight: i64) {
+        let mut hashed = self.expand(element);
+        let weight = weight as u64 & CHUNK_MASK;
+        let mut i = 0;
+        while i < 2688 {
+            let yi = u64::from_le_bytes(hashed[i..i + 8].try_into().unwrap());
+            // the chunks are multiplied one by one, as their products overflow
+            // into the next ones
+            let mut product = 0;
+            for shift in [0, 21, 42] {
+                let chunk = (yi >> shift) & CHUNK_MASK;
+                product |= (chunk.wrapping_mul(weight) & CHUNK_MASK) << shift;
+            }
+            let sum = self.checksum[i / 8].wrapping_add(product) & DATA_MASK;
+            self.checksum[i / 8] = sum;
+            i += 8;
+        }
+        wipe(&mut hashed);
+    }
+
+    fn add_hashed(&mut self, hashed: &[u8; 2688]) {
+        let mut i = 0;
+        while i < 2688 {
+            let xi = &self.checksum[i / 8];
+            let yi = &hashed[i..i + 8];
+            let yi = u64::from_le_bytes(yi.try_into().unwrap()) & DATA_MASK;
+            // the carry of every chunk ends up in its padding bit
+            let sum = xi.wrapping_add(yi) & DATA_MASK;
+            self.checksum[i / 8] = sum;
+            i += 8;
+        }
+    }
+
+    fn sub_hashed(&mut self, hashed: &[u8; 2688]) {
+        let mut i = 0;
+        while i < 2688 {
+            let xi = &self.checksum[i / 8];
+            let yi = &hashed[i..i + 8];
+            let yi = u64::from_le_bytes(yi.try_into().unwrap()) & DATA_MASK;
+            // the borrow of every chunk is taken from its padding bit
+            let diff = (xi | !DATA_MASK).wrapping_sub(yi) & DATA_MASK;
+            self.checksum[i / 8] = diff;
+            i += 8;
+        }
+    }
+
+    #[inline(always)]
+    fn display_hex_ref(&self) -> HexDisplayRef64<'_> {
+        HexDisplayRef64(&self.checksum[..])
+    }
+}
+
+impl<H> Default for LtHash20<H>
+where
+    H: Expander + Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::with_hasher(H::default())
+    }
+}
+
+impl<H: Expander> LtHash for LtHash20<H> {
+    const BYTE_LEN: usize = 2688;
+
+    /// Inserts an element to LtHash, actually it generates the hash (of size 2688 bytes) of the object and sums it to the checksum.
+    fn insert(&mut self, element: impl AsRef<[u8]>) {
+        let mut hashed = self.expand(element);
+        self.add_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Removes an element to LtHash, actually it generates the hash (of size 2688 bytes) of the object and removes it from the checksum.
+    fn remove(&mut self, element: impl AsRef<[u8]>) {
+        let mut hashed = self.expand(element);
+        self.sub_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Provides the hex value as String of the checksum.
+    fn to_hex_string(&self) -> String {
+        self.display_hex_ref().to_string()
+    }
+
+    /// Takes the union of `self` and `rhs`
+    ///
+    /// Equivalent to cloning `self`, then adding all the objects in `rhs`.
+    ///
+    /// Equivalent to `self | other`
+    ///
+    /// # Examples
+    /// ```
+    /// # use lthash_rs::LtHash;
+    /// # use lthash_rs::LtHash20;
+    /// # use sha3::Shake256;
+    /// # use std::iter::FromIterator;
+    /// let mut left = LtHash20::<Shake256>::new();
+    /// left.insert("hello");
+    ///
+    /// let mut right = LtHash20::<Shake256>::new();
+    /// right.insert("world");
+    ///
+    /// assert_eq!(left.union(&right), LtHash20::<Shake256>::from_iter(&["hello", "world"]));
+    /// ```
+    fn union(&self, rhs: &Self) -> Self {
+        let mut checksum: Box<[u64; 336]> = zeroed();
+
+        for (checksum, (&lhs, &rhs)) in checksum
+            .iter_mut()
+            .zip(self.checksum.iter().zip(rhs.checksum.iter()))
+        {
+            *checksum = lhs.wrapping_add(rhs) & DATA_MASK;
+        }
+
+        Self {
+            checksum,
+            hasher: self.hasher.clone(),
+        }
+    }
+
+    /// Takes the difference of `self` and `rhs`.
+    ///
+    /// Equivalent to cloning `self`, then removing all the objects in `rhs`.
+    ///
+    /// Equivalent to `self - other`
+    ///
+    /// # Examples
+    /// ```
+    /// # use lthash_rs::LtHash;
+    /// # use lthash_rs::LtHash20;
+    /// # use sha3::Shake256;
+    /// # use std::iter::FromIterator;
+    /// let mut left = LtHash20::<Shake256>::new();
+    /// left.extend(&["hello", "world"]);
+    ///
+    /// let mut right = LtHash20::<Shake256>::new();
+    /// right.insert("hello");
+    ///
+    /// assert_eq!(left.difference(&right), LtHash20::from_iter(&["world"]));
+    /// ```
+    fn difference(&self, rhs: &Self) -> Self {
+        let mut checksum: Box<[u64; 336]> = zeroed();
+
+        for (checksum, (&lhs, &rhs)) in checksum
+            .iter_mut()
+            .zip(self.checksum.iter().zip(rhs.checksum.iter()))
+        {
+            *checksum = (lhs | !DATA_MASK).wrapping_sub(rhs) & DATA_MASK;
+        }
+
+        Self {
+            checksum,
+            hasher: self.hasher.clone(),
+        }
+    }
+
+    /// Clears the internal checksum
+    fn reset(&mut self) {
+        self.checksum.fill(0);
+    }
+
+    /// Converts self into the inner list of bytes
+    fn into_bytes(self) -> Vec<u8> {
+        into_bytes(&self.checksum[..])
+    }
+
+    /// Returns an empty checksum with the hasher of `self`.
+    fn zero(&self) -> Self {
+        Self::with_hasher(self.hasher.clone())
+    }
+
+    fn with_bytes(&self, bytes: &[u8]) -> Result<Self, String> {
+        Self::read_bytes(bytes, self.hasher.clone())
+    }
+}
+
+/// Reads the bytes returned by [`into_bytes`](LtHash::into_bytes), hashing the
+/// elements with `H::default()`: the key or customization of the hasher the
+/// checksum was computed with is lost, so use [`LtHash::with_bytes`] to keep
+/// hashing with it.
+impl<H: Default> TryFrom<&[u8]> for LtHash20<H> {
+    type Error = String;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::read_bytes(bytes, H::default())
+    }
+}
+
+impl<H> crate::lthash_ref::sealed::Sealed for LtHash20<H> {}
+
+impl<H: Expander> Lanes for LtHash20<H> {
+    const LANE_BITS: u32 = 20;
+    const LANE_COUNT: usize = 1008;
+
+    fn check_bytes(bytes: &[u8]) -> Result<(), String> {
+        if bytes.len() != 2688 {
+            return Err(String::from("Wrong number of bytes."));
+        }
+
+        for word in bytes.chunks_exact(8) {
+            if u64::from_le_bytes(word.try_into().unwrap()) & !DATA_MASK != 0 {
+                return Err(String::from("Padding bits are not zero."));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        for (x, y) in self.checksum.iter_mut().zip(bytes.chunks_exact(8)) {
+            let y = u64::from_le_bytes(y.try_into().unwrap());
+            *x = x.wrapping_add(y) & DATA_MASK;
+        }
+    }
+
+    fn sub_from_bytes(&mut self, bytes: &[u8]) {
+        for (x, y) in self.checksum.iter_mut().zip(bytes.chunks_exact(8)) {
+            let y = u64::from_le_bytes(y.try_into().unwrap());
+            *x = (y | !DATA_MASK).wrapping_sub(*x) & DATA_MASK;
+        }
+    }
+}
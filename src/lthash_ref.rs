@@ -0,0 +1,119 @@
+use std::{fmt, marker::PhantomData};
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+/// The LtHash types whose checksum can be borrowed by a [`LtHashRef`].
+///
+/// This trait is sealed: it is implemented by [`LtHash16`](crate::LtHash16),
+/// [`LtHash20`](crate::LtHash20) and [`LtHash32`](crate::LtHash32) only.
+pub trait Lanes: crate::LtHash + sealed::Sealed {
+    /// Number of bits of each lane (chunk) of the checksum.
+    const LANE_BITS: u32;
+
+    /// Number of lanes of the checksum.
+    const LANE_COUNT: usize;
+
+    /// Checks that `bytes` is a valid checksum.
+    #[doc(hidden)]
+    fn check_bytes(bytes: &[u8]) -> Result<(), String>;
+
+    /// Adds the checksum in `bytes` to `self`.
+    #[doc(hidden)]
+    fn add_bytes(&mut self, bytes: &[u8]);
+
+    /// Replaces `self` with the checksum in `bytes` minus `self`.
+    #[doc(hidden)]
+    fn sub_from_bytes(&mut self, bytes: &[u8]);
+}
+
+/// A checksum borrowed from an existing buffer (a memory-mapped file, a
+/// network packet, …) holding the bytes returned by
+/// [`LtHash::into_bytes`](crate::LtHash::into_bytes).
+///
+/// The bytes are read in place, only the result of [`union`](Self::union) and
+/// [`difference`](Self::difference) is allocated.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{LtHash, LtHash16, LtHashRef};
+/// # use sha3::Shake128;
+/// # use std::iter::FromIterator;
+/// let bytes = LtHash16::<Shake128>::from_iter(["hello"]).into_bytes();
+/// let view = LtHashRef::<LtHash16<Shake128>>::new(&bytes).unwrap();
+///
+/// let right = LtHash16::<Shake128>::from_iter(["world"]);
+/// assert_eq!(view.union(&right), LtHash16::from_iter(["hello", "world"]));
+/// ```
+pub struct LtHashRef<'a, L> {
+    bytes: &'a [u8],
+    lthash: PhantomData<fn() -> L>,
+}
+
+impl<'a, L: Lanes> LtHashRef<'a, L> {
+    /// Borrows the checksum in `bytes`, failing if it is not a valid checksum
+    /// of `L`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, String> {
+        L::check_bytes(bytes)?;
+
+        Ok(Self {
+            bytes,
+            lthash: PhantomData,
+        })
+    }
+
+    /// Returns the borrowed bytes.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Copies the checksum into an owned `L`.
+    pub fn to_lthash(&self) -> L
+    where
+        L: Default,
+    {
+        let mut lthash = L::default();
+        lthash.add_bytes(self.bytes);
+        lthash
+    }
+
+    /// Takes the union of `self` and `rhs`, the result using the hasher of
+    /// `rhs`.
+    pub fn union(&self, rhs: &L) -> L {
+        let mut lthash = rhs.clone();
+        lthash.add_bytes(self.bytes);
+        lthash
+    }
+
+    /// Takes the difference of `self` and `rhs`, the result using the hasher
+    /// of `rhs`.
+    pub fn difference(&self, rhs: &L) -> L {
+        let mut lthash = rhs.clone();
+        lthash.sub_from_bytes(self.bytes);
+        lthash
+    }
+}
+
+impl<'a, L: Lanes> TryFrom<&'a [u8]> for LtHashRef<'a, L> {
+    type Error = String;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::new(bytes)
+    }
+}
+
+impl<L> Clone for LtHashRef<'_, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L> Copy for LtHashRef<'_, L> {}
+
+impl<L> fmt::Debug for LtHashRef<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LtHashRef {:?}", self.bytes)
+    }
+}
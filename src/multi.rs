@@ -0,0 +1,178 @@
+use crate::LtHash;
+
+/// Several LtHash checksums of the same elements, e.g. computed with different
+/// hash functions, held in a tuple of two to four members.
+///
+/// Every operation is forwarded to each member. The bytes are the ones of
+/// each member, in order, preceded by their length as a little-endian `u32`.
+///
+/// To compute an `LtHash16` and an `LtHash32` with the same hash function,
+/// [`LtHashPair`](crate::LtHashPair) hashes each element only once.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{LtHash, LtHash16, LtHash32, MultiLtHash};
+/// # use sha3::{Shake128, Shake256};
+/// # use std::iter::FromIterator;
+/// let mut multi =
+///     MultiLtHash::<(LtHash16<Shake128>, LtHash32<Shake256>)>::default();
+/// multi.extend(["hello", "world"]);
+///
+/// let (shake128, shake256) = multi.into_inner();
+/// assert_eq!(shake128, LtHash16::from_iter(["hello", "world"]));
+/// assert_eq!(shake256, LtHash32::from_iter(["hello", "world"]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MultiLtHash<T>(pub T);
+
+impl<T> MultiLtHash<T> {
+    /// Returns the tuple of checksums.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! tuple {
+    ($($member:ident . $index:tt),+) => {
+        impl<$($member: LtHash),+> LtHash for MultiLtHash<($($member,)+)> {
+            /// The bytes of every checksum preceded by their length.
+            const BYTE_LEN: usize = $(4 + $member::BYTE_LEN +)+ 0;
+
+            /// Inserts an element to every checksum.
+            fn insert(&mut self, element: impl AsRef<[u8]>) {
+                let element = element.as_ref();
+                $(self.0.$index.insert(element);)+
+            }
+
+            /// Removes an element from every checksum.
+            fn remove(&mut self, element: impl AsRef<[u8]>) {
+                let element = element.as_ref();
+                $(self.0.$index.remove(element);)+
+            }
+
+            /// Provides the hex values of the checksums, separated by `:`.
+            fn to_hex_string(&self) -> String {
+                [$(self.0.$index.to_hex_string()),+].join(":")
+            }
+
+            fn union(&self, rhs: &Self) -> Self {
+                Self(($(self.0.$index.union(&rhs.0.$index),)+))
+            }
+
+            fn difference(&self, rhs: &Self) -> Self {
+                Self(($(self.0.$index.difference(&rhs.0.$index),)+))
+            }
+
+            fn reset(&mut self) {
+                $(self.0.$index.reset();)+
+            }
+
+            /// Converts self into the bytes of every checksum, each one
+            /// preceded by its length as a little-endian `u32`.
+            fn into_bytes(self) -> Vec<u8> {
+                let mut bytes = Vec::new();
+                $(
+                    let member = self.0.$index.into_bytes();
+                    bytes.extend_from_slice(&(member.len() as u32).to_le_bytes());
+                    bytes.extend_from_slice(&member);
+                )+
+                bytes
+            }
+
+            fn zero(&self) -> Self {
+                Self(($(self.0.$index.zero(),)+))
+            }
+
+            fn with_bytes(&self, bytes: &[u8]) -> Result<Self, String> {
+                let mut rest = bytes;
+                let this = Self(($(
+                    self.0.$index.with_bytes(take_member(&mut rest)?)?,
+                )+));
+
+                if !rest.is_empty() {
+                    return Err(String::from("Trailing bytes."));
+                }
+                Ok(this)
+            }
+        }
+
+        impl<$($member),+> TryFrom<&[u8]> for MultiLtHash<($($member,)+)>
+        where
+            $($member: for<'a> TryFrom<&'a [u8], Error = String>),+
+        {
+            type Error = String;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                let mut rest = bytes;
+                let this = Self(($(
+                    $member::try_from(take_member(&mut rest)?)?,
+                )+));
+
+                if !rest.is_empty() {
+                    return Err(String::from("Trailing bytes."));
+                }
+                Ok(this)
+            }
+        }
+    };
+}
+
+tuple!(A.0, B.1);
+tuple!(A.0, B.1, C.2);
+tuple!(A.0, B.1, C.2, D.3);
+
+fn take_member<'a>(rest: &mut &'a [u8]) -> Result<&'a [u8], String> {
+    let truncated = || String::from("Truncated checksums.");
+    let (len, tail) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+    let len = u32::from_le_bytes(*len) as usize;
+    let (member, tail) = tail.split_at_checked(len).ok_or_else(truncated)?;
+    *rest = tail;
+    Ok(member)
+}
+
+impl<A, T> Extend<A> for MultiLtHash<T>
+where
+    A: AsRef<[u8]>,
+    Self: LtHash,
+{
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<A, T> FromIterator<A> for MultiLtHash<T>
+where
+    A: AsRef<[u8]>,
+    Self: LtHash + Default,
+{
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+        let mut this = Self::default();
+        this.extend(iter);
+        this
+    }
+}
+
+impl<T> std::ops::BitOr for &MultiLtHash<T>
+where
+    MultiLtHash<T>: LtHash,
+{
+    type Output = MultiLtHash<T>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl<T> std::ops::Sub for &MultiLtHash<T>
+where
+    MultiLtHash<T>: LtHash,
+{
+    type Output = MultiLtHash<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{utils::wipe, Expander, LtHash, LtHash16, LtHash32, PrefixStable};
+
+/// An `LtHash16` and an `LtHash32` of the same elements, maintained in one
+/// pass to migrate from one to the other.
+///
+/// Each element is hashed once, into 4096 bytes summed to the `LtHash32`,
+/// whose first 2048 bytes are summed to the `LtHash16`. Both checksums are
+/// thus the ones computed separately only if the output of `H` does not
+/// depend on its length, so `H` must be [`PrefixStable`].
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "sha3")] {
+/// # use lthash_rs::{LtHash, LtHash16, LtHash32, LtHashPair};
+/// # use sha3::Shake128;
+/// # use std::iter::FromIterator;
+/// let mut pair = LtHashPair::<Shake128>::new();
+/// pair.extend(["hello", "world"]);
+///
+/// assert_eq!(pair.lthash16(), &LtHash16::from_iter(["hello", "world"]));
+/// assert_eq!(pair.lthash32(), &LtHash32::from_iter(["hello", "world"]));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LtHashPair<H> {
+    lthash16: LtHash16<H>,
+    lthash32: LtHash32<H>,
+}
+
+impl<H> LtHashPair<H> {
+    /// Creates an empty pair of checksums hashing the objects with clones of
+    /// `hasher`.
+    pub fn with_hasher(hasher: H) -> Self
+    where
+        H: Clone,
+    {
+        Self {
+            lthash16: LtHash16::with_hasher(hasher.clone()),
+            lthash32: LtHash32::with_hasher(hasher),
+        }
+    }
+
+    /// Pairs checksums of the same elements, e.g. deserialized separately.
+    #[inline(always)]
+    pub fn from_parts(lthash16: LtHash16<H>, lthash32: LtHash32<H>) -> Self {
+        Self { lthash16, lthash32 }
+    }
+
+    /// Returns the `LtHash16` and the `LtHash32`.
+    #[inline(always)]
+    pub fn into_parts(self) -> (LtHash16<H>, LtHash32<H>) {
+        (self.lthash16, self.lthash32)
+    }
+
+    /// Returns the `LtHash16`.
+    #[inline(always)]
+    pub fn lthash16(&self) -> &LtHash16<H> {
+        &self.lthash16
+    }
+
+    /// Returns the `LtHash32`.
+    #[inline(always)]
+    pub fn lthash32(&self) -> &LtHash32<H> {
+        &self.lthash32
+    }
+}
+
+impl<H> LtHashPair<H>
+where
+    H: Expander + PrefixStable + Default + Clone,
+{
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<H> LtHashPair<H>
+where
+    H: Expander + PrefixStable + Clone,
+{
+    fn finalize(&self, element: impl AsRef<[u8]>) -> [u8; 4096] {
+        let mut output = [0u8; 4096];
+        self.lthash32.hasher().expand(element.as_ref(), &mut output);
+        output
+    }
+}
+
+impl<H> Default for LtHashPair<H>
+where
+    H: Expander + PrefixStable + Default + Clone,
+{
+    fn default() -> Self {
+        Self::with_hasher(H::default())
+    }
+}
+
+impl<H> LtHash for LtHashPair<H>
+where
+    H: Expander + PrefixStable + Clone,
+{
+    /// The bytes of the `LtHash16` followed by the ones of the `LtHash32`.
+    const BYTE_LEN: usize = 2048 + 4096;
+
+    /// Inserts an element to both checksums, hashing it once.
+    fn insert(&mut self, element: impl AsRef<[u8]>) {
+        let mut hashed = self.finalize(element);
+        let (prefix, _) = hashed.split_first_chunk::<2048>().unwrap();
+        self.lthash16.add_hashed(prefix);
+        self.lthash32.add_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Removes an element from both checksums, hashing it once.
+    fn remove(&mut self, element: impl AsRef<[u8]>) {
+        let mut hashed = self.finalize(element);
+        let (prefix, _) = hashed.split_first_chunk::<2048>().unwrap();
+        self.lthash16.sub_hashed(prefix);
+        self.lthash32.sub_hashed(&hashed);
+        wipe(&mut hashed);
+    }
+
+    /// Provides the hex value of the `LtHash16` followed by the one of the
+    /// `LtHash32`.
+    fn to_hex_string(&self) -> String {
+        self.lthash16.to_hex_string() + &self.lthash32.to_hex_string()
+    }
+
+    fn union(&self, rhs: &Self) -> Self {
+        Self {
+            lthash16: self.lthash16.union(&rhs.lthash16),
+            lthash32: self.lthash32.union(&rhs.lthash32),
+        }
+    }
+
+    fn difference(&self, rhs: &Self) -> Self {
+        Self {
+            lthash16: self.lthash16.difference(&rhs.lthash16),
+            lthash32: self.lthash32.difference(&rhs.lthash32),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.lthash16.reset();
+        self.lthash32.reset();
+    }
+
+    /// Converts self into the 2048 bytes of the `LtHash16` followed by the
+    /// 4096 bytes of the `LtHash32`.
+    fn into_bytes(self) -> Vec<u8> {
+        let mut bytes = self.lthash16.into_bytes();
+        bytes.extend(self.lthash32.into_bytes());
+        bytes
+    }
+
+    fn zero(&self) -> Self {
+        Self {
+            lthash16: self.lthash16.zero(),
+            lthash32: self.lthash32.zero(),
+        }
+    }
+
+    fn with_bytes(&self, bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != Self::BYTE_LEN {
+            return Err(String::from("Wrong number of bytes."));
+        }
+        let (lthash16, lthash32) = bytes.split_at(2048);
+
+        Ok(Self {
+            lthash16: self.lthash16.with_bytes(lthash16)?,
+            lthash32: self.lthash32.with_bytes(lthash32)?,
+        })
+    }
+}
+
+impl<H: Default> TryFrom<&[u8]> for LtHashPair<H> {
+    type Error = String;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != 2048 + 4096 {
+            return Err(String::from("Wrong number of bytes."));
+        }
+        let (lthash16, lthash32) = bytes.split_at(2048);
+
+        Ok(Self {
+            lthash16: LtHash16::try_from(lthash16)?,
+            lthash32: LtHash32::try_from(lthash32)?,
+        })
+    }
+}
+
+impl<H> PartialEq for LtHashPair<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.lthash16 == other.lthash16 && self.lthash32 == other.lthash32
+    }
+}
+
+impl<H> std::fmt::Debug for LtHashPair<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LtHashPair")
+            .field(&self.lthash16)
+            .field(&self.lthash32)
+            .finish()
+    }
+}
+
+impl<A, H> Extend<A> for LtHashPair<H>
+where
+    A: AsRef<[u8]>,
+    H: Expander + PrefixStable + Clone,
+{
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<A, H> FromIterator<A> for LtHashPair<H>
+where
+    A: AsRef<[u8]>,
+    H: Expander + PrefixStable + Default + Clone,
+{
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        let mut this = Self::default();
+        this.extend(iter);
+        this
+    }
+}
+
+impl<H> std::ops::BitOr for &LtHashPair<H>
+where
+    H: Expander + PrefixStable + Clone,
+{
+    type Output = LtHashPair<H>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl<H> std::ops::Sub for &LtHashPair<H>
+where
+    H: Expander + PrefixStable + Clone,
+{
+    type Output = LtHashPair<H>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
@@ -0,0 +1,235 @@
+use crate::LtHash;
+
+/// A LtHash checksum of a sequence, where the order of the elements matters.
+///
+/// Each element is bound to its position by inserting the position, as a
+/// little-endian `u64`, followed by the element. The positions start at an
+/// offset, so that the checksums of consecutive segments of a sequence (e.g.
+/// of a log) can be [concatenated](Self::concat).
+///
+/// As the elements are not kept, those removed by [`pop`](Self::pop),
+/// [`replace`](Self::replace) and [`splice`](Self::splice) must be given
+/// back. The operations fail if a position overflows a `u64`.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{LtHash16, SequenceLtHash};
+/// # use sha3::Shake128;
+/// type Sequence = SequenceLtHash<LtHash16<Shake128>>;
+///
+/// let mut ab = Sequence::default();
+/// ab.extend(["a", "b"]);
+/// let mut ba = Sequence::default();
+/// ba.extend(["b", "a"]);
+/// assert_ne!(ab, ba);
+///
+/// let mut c = Sequence::with_offset(2);
+/// c.push("c").unwrap();
+/// let mut abc = Sequence::default();
+/// abc.extend(["a", "b", "c"]);
+/// assert_eq!(ab.concat(&c).unwrap(), abc);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SequenceLtHash<L> {
+    lthash: L,
+    offset: u64,
+    len: u64,
+}
+
+impl<L> SequenceLtHash<L> {
+    /// Builds the checksum of the `len` elements starting at position `offset`
+    /// summed in `lthash`.
+    #[inline(always)]
+    pub fn from_parts(lthash: L, offset: u64, len: u64) -> Self {
+        Self {
+            lthash,
+            offset,
+            len,
+        }
+    }
+
+    /// Returns the checksum, the offset and the number of elements.
+    #[inline(always)]
+    pub fn into_parts(self) -> (L, u64, u64) {
+        (self.lthash, self.offset, self.len)
+    }
+
+    /// Returns the checksum.
+    #[inline(always)]
+    pub fn lthash(&self) -> &L {
+        &self.lthash
+    }
+
+    /// Returns the position of the first element.
+    #[inline(always)]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the number of elements.
+    #[inline(always)]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the sequence is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<L: Default> SequenceLtHash<L> {
+    /// Creates an empty sequence whose first element is at position `offset`.
+    pub fn with_offset(offset: u64) -> Self {
+        Self::from_parts(L::default(), offset, 0)
+    }
+}
+
+impl<L: LtHash> SequenceLtHash<L> {
+    /// Appends an element to the sequence.
+    ///
+    /// Fails if its position overflows.
+    pub fn push(&mut self, element: impl AsRef<[u8]>) -> Result<(), String> {
+        self.lthash.insert(self.bind(self.len, element)?);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the last element, which must be `element`, from the sequence.
+    ///
+    /// Fails if the sequence is empty.
+    pub fn pop(&mut self, element: impl AsRef<[u8]>) -> Result<(), String> {
+        if self.len == 0 {
+            return Err(String::from("The sequence is empty."));
+        }
+        self.lthash.remove(self.bind(self.len - 1, element)?);
+        self.len -= 1;
+        Ok(())
+    }
+
+    /// Replaces `old`, the element at `index` (from the start of the
+    /// sequence), with `new`.
+    ///
+    /// Fails if `index` is out of bounds.
+    pub fn replace(
+        &mut self,
+        index: u64,
+        old: impl AsRef<[u8]>,
+        new: impl AsRef<[u8]>,
+    ) -> Result<(), String> {
+        if index >= self.len {
+            return Err(out_of_bounds(index, self.len));
+        }
+        self.lthash.remove(self.bind(index, old)?);
+        self.lthash.insert(self.bind(index, new)?);
+        Ok(())
+    }
+
+    /// Replaces the `removed` elements, starting at `index` (from the start
+    /// of the sequence), with the `inserted` ones.
+    ///
+    /// Unless as many elements are inserted as removed, the following ones
+    /// would move, so the removed elements must then reach the end of the
+    /// sequence. Fails, leaving the sequence untouched, if they do not or if
+    /// they go past it.
+    pub fn splice<A, B>(
+        &mut self,
+        index: u64,
+        removed: impl IntoIterator<Item = A>,
+        inserted: impl IntoIterator<Item = B>,
+    ) -> Result<(), String>
+    where
+        A: AsRef<[u8]>,
+        B: AsRef<[u8]>,
+    {
+        if index > self.len {
+            return Err(out_of_bounds(index, self.len));
+        }
+
+        // applied once checked
+        let mut delta = self.lthash.zero();
+        let mut end = index;
+        for element in removed {
+            if end == self.len {
+                return Err(String::from(
+                    "The removed elements go past the end of the sequence.",
+                ));
+            }
+            delta.remove(self.bind(end, element)?);
+            end += 1;
+        }
+        let mut inserted_end = index;
+        for element in inserted {
+            delta.insert(self.bind(inserted_end, element)?);
+            inserted_end += 1;
+        }
+        if inserted_end != end && end != self.len {
+            return Err(String::from(
+                "The removed elements must reach the end of the sequence when \
+                 their number differs from the number of inserted ones.",
+            ));
+        }
+
+        self.lthash = self.lthash.union(&delta);
+        self.len = self.len - end + inserted_end;
+        Ok(())
+    }
+
+    /// Concatenates `self` and `rhs`, which must start where `self` ends.
+    pub fn concat(&self, rhs: &Self) -> Result<Self, String> {
+        let end = self.position(self.len)?;
+        if rhs.offset != end {
+            return Err(format!(
+                "The sequence starts at {} instead of {end}.",
+                rhs.offset
+            ));
+        }
+        let len = self.len.checked_add(rhs.len).ok_or_else(|| {
+            format!("The length {} + {} overflows a u64.", self.len, rhs.len)
+        })?;
+        Ok(Self {
+            lthash: self.lthash.union(&rhs.lthash),
+            offset: self.offset,
+            len,
+        })
+    }
+
+    fn position(&self, index: u64) -> Result<u64, String> {
+        self.offset.checked_add(index).ok_or_else(|| {
+            format!("The position {} + {index} overflows a u64.", self.offset)
+        })
+    }
+
+    fn bind(
+        &self,
+        index: u64,
+        element: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>, String> {
+        let element = element.as_ref();
+        let mut bound = Vec::with_capacity(8 + element.len());
+        bound.extend_from_slice(&self.position(index)?.to_le_bytes());
+        bound.extend_from_slice(element);
+        Ok(bound)
+    }
+}
+
+/// # Panics
+/// Panics if a position overflows.
+impl<A, L> Extend<A> for SequenceLtHash<L>
+where
+    A: AsRef<[u8]>,
+    L: LtHash,
+{
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        for item in iter {
+            if let Err(error) = self.push(item) {
+                panic!("{error}");
+            }
+        }
+    }
+}
+
+fn out_of_bounds(index: u64, len: u64) -> String {
+    format!("The index {index} is out of bounds for a sequence of {len}.")
+}
@@ -0,0 +1,472 @@
+//! On-disk store of many keyed [`LtHash32`] checksums (`store` feature).
+//!
+//! The file is made of a header, the checksums as fixed records of 4096 bytes
+//! and the index of their keys:
+//!
+//! | Offset         | Length   | Content                                    |
+//! | -------------- | -------- | ------------------------------------------ |
+//! | 0              | 8        | magic `LTHASH32`                           |
+//! | 8              | 4        | version (1), little-endian                 |
+//! | 12             | 4        | length of a record (4096), little-endian   |
+//! | 16             | 8        | number of records `n`, little-endian       |
+//! | 24             | 2        | id of the hash function, see [`XofId`]     |
+//! | 26             | 6        | reserved (0)                               |
+//! | 32             | n × 4096 | the checksums, as returned by `into_bytes` |
+//! | 32 + n × 4096  |          | `n` × (length u32 LE, key)                 |
+//!
+//! [`Store::sync`] first writes the changes to a write-ahead log next to the
+//! store (its path followed by `.wal`), then applies them in place:
+//!
+//! | Length | Content                                               |
+//! | ------ | ----------------------------------------------------- |
+//! | 8      | magic `LTHASHWL`                                      |
+//! | 8      | length of the store once the changes are applied      |
+//! | 4      | number of changes                                     |
+//! |        | every change: offset (u64), length (u32) and bytes    |
+//! | 4      | CRC-32 of all the previous bytes                      |
+//!
+//! All the integers are little-endian. A complete log left by a crash is
+//! applied again when the store is opened, and an incomplete one discarded.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use memmap2::{MmapMut, MmapOptions};
+
+use crate::{Expander, Lanes, LtHash, LtHash32, LtHashRef, XofId};
+
+const MAGIC: &[u8; 8] = b"LTHASH32";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 32;
+const RECORD_LEN: usize = 4096;
+const WAL_MAGIC: &[u8; 8] = b"LTHASHWL";
+
+/// Bytes to write at an offset of the store.
+type Change = (usize, Vec<u8>);
+
+/// Many [`LtHash32`] checksums identified by a key, stored in a memory-mapped
+/// file.
+///
+/// [`insert`](Self::insert) and [`remove`](Self::remove) update copies of the
+/// records in memory, which [`sync`](Self::sync) writes in place, through a
+/// write-ahead log so that they are never partially written even if the
+/// process crashes. A sync only writes the changed records, the new ones and
+/// the index of the keys.
+///
+/// The store records the [`XofId`] of its hash function, and cannot be opened
+/// with another one. Only the function is identified, not its key or
+/// customization string.
+///
+/// The file must not be modified by other processes, nor opened by another
+/// `Store`, while it is open.
+///
+/// # Examples
+/// ```no_run
+/// # use lthash_rs::{store::Store, Blake2xb};
+/// let mut store = Store::<Blake2xb>::open("partitions.lthash")?;
+/// store.insert("partition-1", "hello");
+/// store.insert("partition-2", "world");
+/// store.sync()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Store<H> {
+    path: PathBuf,
+    file: File,
+    map: MmapMut,
+    /// Number of records in `map`, the following ones being only in `dirty`.
+    mapped: usize,
+    /// The records changed since the last sync.
+    dirty: HashMap<usize, Box<[u8; RECORD_LEN]>>,
+    keys: Vec<Vec<u8>>,
+    index: HashMap<Vec<u8>, usize>,
+    hasher: H,
+}
+
+impl<H: XofId + Default> Store<H> {
+    /// Opens the store at `path`, creating an empty one if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with_hasher(path, H::default())
+    }
+}
+
+impl<H: XofId> Store<H> {
+    /// Opens the store at `path`, creating an empty one if it doesn't exist,
+    /// hashing the objects with clones of `hasher`.
+    ///
+    /// Fails if the store was created with another hash function.
+    pub fn open_with_hasher(
+        path: impl AsRef<Path>,
+        hasher: H,
+    ) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            let temp = temp_path(&path, ".tmp");
+            write_file(&temp, |writer| {
+                write_header(writer, 0, H::XOF_ID)?;
+                writer.write_all(&[0; HEADER_LEN - 26])
+            })?;
+            fs::rename(&temp, &path)?;
+            sync_dir(&path)?;
+        }
+
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        recover(&file, &path)?;
+        let map = map_file(&file)?;
+        let keys = read_keys(&map, H::XOF_ID)?;
+
+        let mut index = HashMap::with_capacity(keys.len());
+        for (i, key) in keys.iter().enumerate() {
+            if index.insert(key.clone(), i).is_some() {
+                return Err(invalid_data("Duplicate key."));
+            }
+        }
+
+        Ok(Self {
+            path,
+            file,
+            map,
+            mapped: keys.len(),
+            dirty: HashMap::new(),
+            keys,
+            index,
+            hasher,
+        })
+    }
+}
+
+impl<H> Store<H> {
+    /// Returns the number of checksums.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if there are no checksums.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the keys, in the order of the records.
+    pub fn keys(&self) -> impl Iterator<Item = &[u8]> {
+        self.keys.iter().map(Vec::as_slice)
+    }
+
+    /// Returns `true` if there is a checksum for `key`.
+    pub fn contains_key(&self, key: impl AsRef<[u8]>) -> bool {
+        self.index.contains_key(key.as_ref())
+    }
+
+    /// Borrows the checksum of `key`, without copying it.
+    pub fn get(
+        &self,
+        key: impl AsRef<[u8]>,
+    ) -> Option<LtHashRef<'_, LtHash32<H>>>
+    where
+        H: Expander,
+    {
+        let &i = self.index.get(key.as_ref())?;
+        let record = LtHashRef::new(self.record(i))
+            .expect("records have the length of a checksum");
+        Some(record)
+    }
+
+    fn record(&self, i: usize) -> &[u8] {
+        match self.dirty.get(&i) {
+            Some(record) => &record[..],
+            None => {
+                let offset = HEADER_LEN + i * RECORD_LEN;
+                &self.map[offset..offset + RECORD_LEN]
+            }
+        }
+    }
+
+    fn record_mut(&mut self, key: &[u8]) -> &mut [u8] {
+        let i = match self.index.get(key) {
+            Some(&i) => i,
+            None => {
+                let i = self.keys.len();
+                self.keys.push(key.to_vec());
+                self.index.insert(key.to_vec(), i);
+                self.dirty.insert(i, Box::new([0; RECORD_LEN]));
+                i
+            }
+        };
+
+        let map = &self.map;
+        let record = self.dirty.entry(i).or_insert_with(|| {
+            let offset = HEADER_LEN + i * RECORD_LEN;
+            Box::new(map[offset..offset + RECORD_LEN].try_into().unwrap())
+        });
+        &mut record[..]
+    }
+
+    /// Writes the changes since the last sync to the file, atomically.
+    ///
+    /// The changes are written to the write-ahead log, which is fsynced, then
+    /// in place to the store, which is flushed before the log is removed.
+    pub fn sync(&mut self) -> io::Result<()> {
+        if self.dirty.is_empty() {
+            return Ok(());
+        }
+
+        let mut changes: Vec<Change> = Vec::new();
+        for (&i, record) in &self.dirty {
+            if i < self.mapped {
+                changes.push((HEADER_LEN + i * RECORD_LEN, record.to_vec()));
+            }
+        }
+        let mut len = self.map.len();
+        if self.keys.len() > self.mapped {
+            // the new records move the index of the keys, rewritten after them
+            let mut tail = Vec::new();
+            for i in self.mapped..self.keys.len() {
+                tail.extend_from_slice(&self.dirty[&i][..]);
+            }
+            for key in &self.keys {
+                tail.extend_from_slice(&(key.len() as u32).to_le_bytes());
+                tail.extend_from_slice(key);
+            }
+            let offset = HEADER_LEN + self.mapped * RECORD_LEN;
+            len = offset + tail.len();
+            changes.push((16, (self.keys.len() as u64).to_le_bytes().to_vec()));
+            changes.push((offset, tail));
+        }
+
+        let wal = temp_path(&self.path, ".wal");
+        write_file(&wal, |writer| write_wal(writer, len, &changes))?;
+        sync_dir(&self.path)?;
+
+        self.map = apply(&self.file, &mut self.map, len, &changes)?;
+        self.mapped = self.keys.len();
+        self.dirty.clear();
+
+        fs::remove_file(&wal)?;
+        sync_dir(&self.path)
+    }
+}
+
+impl<H> Store<H>
+where
+    H: Expander,
+{
+    /// Inserts `element` to the checksum of `key`, creating an empty one if
+    /// there is none.
+    pub fn insert(&mut self, key: impl AsRef<[u8]>, element: impl AsRef<[u8]>) {
+        self.update(key.as_ref(), |lthash| lthash.insert(element));
+    }
+
+    /// Removes `element` from the checksum of `key`, creating an empty one if
+    /// there is none.
+    pub fn remove(&mut self, key: impl AsRef<[u8]>, element: impl AsRef<[u8]>) {
+        self.update(key.as_ref(), |lthash| lthash.remove(element));
+    }
+
+    fn update(&mut self, key: &[u8], f: impl FnOnce(&mut LtHash32<H>)) {
+        let mut lthash = LtHash32::with_hasher(self.hasher.clone());
+        let record = self.record_mut(key);
+        lthash.add_bytes(record);
+        f(&mut lthash);
+        record.copy_from_slice(&lthash.into_bytes());
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_header(
+    writer: &mut impl Write,
+    records: usize,
+    xof_id: u16,
+) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&(RECORD_LEN as u32).to_le_bytes())?;
+    writer.write_all(&(records as u64).to_le_bytes())?;
+    writer.write_all(&xof_id.to_le_bytes())
+}
+
+/// Maps `file`, shared so that the changes are written to it.
+fn map_file(file: &File) -> io::Result<MmapMut> {
+    // SAFETY: the file must not be modified by other processes, nor mapped by
+    // another store, while the store is open, as documented in `Store`.
+    unsafe { MmapOptions::new().map_mut(file) }
+}
+
+/// Checks the header of the store and returns the keys of the records.
+fn read_keys(map: &[u8], xof_id: u16) -> io::Result<Vec<Vec<u8>>> {
+    if map.len() < HEADER_LEN || &map[..8] != MAGIC {
+        return Err(invalid_data("Not a checksum store."));
+    }
+    let read_u32 = |offset: usize| {
+        u32::from_le_bytes(map[offset..offset + 4].try_into().unwrap())
+    };
+    if read_u32(8) != VERSION {
+        return Err(invalid_data("Unsupported version."));
+    }
+    if read_u32(12) as usize != RECORD_LEN {
+        return Err(invalid_data("Wrong length of the records."));
+    }
+    let found = u16::from_le_bytes([map[24], map[25]]);
+    if found != xof_id {
+        return Err(invalid_data(&format!(
+            "Expected hash function {xof_id}, found {found}."
+        )));
+    }
+
+    let records = u64::from_le_bytes(map[16..24].try_into().unwrap());
+    let mut offset = usize::try_from(records)
+        .ok()
+        .and_then(|records| records.checked_mul(RECORD_LEN))
+        .and_then(|len| len.checked_add(HEADER_LEN))
+        .filter(|&end| end <= map.len())
+        .ok_or_else(|| invalid_data("Truncated records."))?;
+
+    let mut keys = Vec::with_capacity(records as usize);
+    for _ in 0..records {
+        if offset + 4 > map.len() {
+            return Err(invalid_data("Truncated key index."));
+        }
+        let len = read_u32(offset) as usize;
+        offset += 4;
+        let key = map
+            .get(offset..offset + len)
+            .ok_or_else(|| invalid_data("Truncated key index."))?;
+        keys.push(key.to_vec());
+        offset += len;
+    }
+
+    if offset != map.len() {
+        return Err(invalid_data("Trailing bytes."));
+    }
+
+    Ok(keys)
+}
+
+fn write_wal(
+    writer: &mut impl Write,
+    len: usize,
+    changes: &[Change],
+) -> io::Result<()> {
+    let mut wal = WAL_MAGIC.to_vec();
+    wal.extend_from_slice(&(len as u64).to_le_bytes());
+    wal.extend_from_slice(&(changes.len() as u32).to_le_bytes());
+    for (offset, bytes) in changes {
+        wal.extend_from_slice(&(*offset as u64).to_le_bytes());
+        wal.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        wal.extend_from_slice(bytes);
+    }
+    let crc = crc32fast::hash(&wal);
+    wal.extend_from_slice(&crc.to_le_bytes());
+    writer.write_all(&wal)
+}
+
+/// Reads a write-ahead log, returning `None` if it is incomplete or corrupt.
+fn read_wal(wal: &[u8]) -> Option<(usize, Vec<Change>)> {
+    let (wal, crc) = wal.split_last_chunk::<4>()?;
+    if crc32fast::hash(wal).to_le_bytes() != *crc {
+        return None;
+    }
+    let rest = wal.strip_prefix(WAL_MAGIC)?;
+    let (len, mut rest) = rest.split_first_chunk::<8>()?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+    let (count, tail) = rest.split_first_chunk::<4>()?;
+    rest = tail;
+
+    let mut changes = Vec::new();
+    for _ in 0..u32::from_le_bytes(*count) {
+        let (offset, tail) = rest.split_first_chunk::<8>()?;
+        let offset = usize::try_from(u64::from_le_bytes(*offset)).ok()?;
+        let (bytes_len, tail) = tail.split_first_chunk::<4>()?;
+        let (bytes, tail) =
+            tail.split_at_checked(u32::from_le_bytes(*bytes_len) as usize)?;
+        if offset.checked_add(bytes.len())? > len {
+            return None;
+        }
+        changes.push((offset, bytes.to_vec()));
+        rest = tail;
+    }
+
+    rest.is_empty().then_some((len, changes))
+}
+
+/// Applies the changes of a write-ahead log to the store, returning its new
+/// mapping once flushed.
+fn apply(
+    file: &File,
+    map: &mut MmapMut,
+    len: usize,
+    changes: &[Change],
+) -> io::Result<MmapMut> {
+    map.flush()?;
+    file.set_len(len as u64)?;
+    let mut map = map_file(file)?;
+    for (offset, bytes) in changes {
+        map[*offset..*offset + bytes.len()].copy_from_slice(bytes);
+    }
+    map.flush()?;
+    file.sync_all()?;
+    Ok(map)
+}
+
+/// Applies the write-ahead log of the store at `path` if it is complete, and
+/// removes it.
+fn recover(file: &File, path: &Path) -> io::Result<()> {
+    let wal = temp_path(path, ".wal");
+    let bytes = match fs::read(&wal) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    if let Some((len, changes)) = read_wal(&bytes) {
+        let mut map = map_file(file)?;
+        apply(file, &mut map, len, &changes)?;
+    }
+    fs::remove_file(&wal)?;
+    sync_dir(path)
+}
+
+fn temp_path(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(extension);
+    path.with_file_name(name)
+}
+
+/// Writes a new file at `path` and fsyncs it.
+fn write_file(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<&File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    let mut writer = BufWriter::new(&file);
+    write(&mut writer)?;
+    writer.flush()?;
+    drop(writer);
+    file.sync_all()
+}
+
+/// Fsyncs the directory of `path`, making its renaming durable.
+fn sync_dir(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        File::open(dir)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+//! Integration with the asynchronous traits of `futures` (`futures` feature).
+
+use std::{
+    convert::Infallible,
+    future::Future,
+    io,
+    pin::{pin, Pin},
+    task::{Context, Poll},
+};
+
+use digest::Update;
+use futures::{io::AsyncWrite, Sink, Stream, StreamExt};
+
+use crate::{Expander, LtHash, LtHash16, LtHash20, LtHash32, XofExpander};
+
+/// Collects a [`Stream`] of elements into a checksum, like
+/// [`FromIterator`] does for iterators.
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{LtHash16, LtHashStreamExt};
+/// # use sha3::Shake128;
+/// # use std::iter::FromIterator;
+/// let stream = futures::stream::iter(["hello", "world"]);
+/// let lthash = futures::executor::block_on(
+///     stream.collect_lthash::<LtHash16<Shake128>>(),
+/// );
+/// assert_eq!(lthash, LtHash16::from_iter(["hello", "world"]));
+/// ```
+pub trait LtHashStreamExt: Stream + Sized {
+    /// Inserts all the elements of the stream to an empty checksum.
+    fn collect_lthash<L>(self) -> impl Future<Output = L>
+    where
+        L: LtHash + Default,
+        Self::Item: AsRef<[u8]>,
+    {
+        self.extend_lthash(L::default())
+    }
+
+    /// Inserts all the elements of the stream to `lthash`.
+    fn extend_lthash<L>(self, mut lthash: L) -> impl Future<Output = L>
+    where
+        L: LtHash,
+        Self::Item: AsRef<[u8]>,
+    {
+        async move {
+            let mut stream = pin!(self);
+            while let Some(element) = stream.next().await {
+                lthash.insert(element);
+            }
+            lthash
+        }
+    }
+}
+
+impl<S: Stream> LtHashStreamExt for S {}
+
+/// The checksums whose elements can be written incrementally by an
+/// [`ElementWriter`].
+///
+/// This trait is sealed: it is implemented by [`LtHash16`], [`LtHash20`] and
+/// [`LtHash32`] only, hashing with an [`XofExpander`] since the element is fed
+/// to it piece by piece.
+pub trait IncrementalLtHash:
+    LtHash + crate::lthash_ref::sealed::Sealed + Sized
+{
+    /// The hash function.
+    #[doc(hidden)]
+    type Hasher: Update;
+
+    #[doc(hidden)]
+    fn element_hasher(&self) -> Self::Hasher;
+
+    #[doc(hidden)]
+    fn insert_hashed(&mut self, hasher: Self::Hasher);
+
+    #[doc(hidden)]
+    fn remove_hashed(&mut self, hasher: Self::Hasher);
+
+    /// Returns an [`AsyncWrite`] inserting the bytes written to it to `self`,
+    /// as a single element, once closed.
+    fn insert_writer(&mut self) -> ElementWriter<'_, Self> {
+        ElementWriter::new(self, false)
+    }
+
+    /// Returns an [`AsyncWrite`] removing the bytes written to it from
+    /// `self`, as a single element, once closed.
+    fn remove_writer(&mut self) -> ElementWriter<'_, Self> {
+        ElementWriter::new(self, true)
+    }
+}
+
+macro_rules! incremental {
+    ($lthash:ident) => {
+        impl<H> IncrementalLtHash for $lthash<H>
+        where
+            H: XofExpander,
+        {
+            type Hasher = H;
+
+            fn element_hasher(&self) -> H {
+                self.hasher().clone()
+            }
+
+            fn insert_hashed(&mut self, hasher: H) {
+                $lthash::insert_hashed(self, hasher);
+            }
+
+            fn remove_hashed(&mut self, hasher: H) {
+                $lthash::remove_hashed(self, hasher);
+            }
+        }
+
+        /// Inserts the items sent to the sink.
+        impl<A, H> Sink<A> for $lthash<H>
+        where
+            A: AsRef<[u8]>,
+            H: Expander + Unpin,
+        {
+            type Error = Infallible;
+
+            fn poll_ready(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+            ) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn start_send(
+                self: Pin<&mut Self>,
+                item: A,
+            ) -> Result<(), Self::Error> {
+                self.get_mut().insert(item);
+                Ok(())
+            }
+
+            fn poll_flush(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+            ) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+            ) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+    };
+}
+
+incremental!(LtHash16);
+incremental!(LtHash20);
+incremental!(LtHash32);
+
+/// Hashes an element written in parts, like a large object copied from a file
+/// or a socket, and inserts it to (or removes it from) a checksum once closed.
+///
+/// The element is discarded if the writer is dropped without being closed.
+///
+/// # Examples
+/// ```
+/// # use futures::io::AsyncWriteExt;
+/// # use lthash_rs::{IncrementalLtHash, LtHash16};
+/// # use sha3::Shake128;
+/// # use std::iter::FromIterator;
+/// let mut lthash = LtHash16::<Shake128>::new();
+/// futures::executor::block_on(async {
+///     let mut writer = lthash.insert_writer();
+///     writer.write_all(b"hello ").await?;
+///     writer.write_all(b"world").await?;
+///     writer.close().await
+/// })?;
+/// assert_eq!(lthash, LtHash16::from_iter(["hello world"]));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct ElementWriter<'a, L: IncrementalLtHash> {
+    lthash: &'a mut L,
+    hasher: Option<L::Hasher>,
+    remove: bool,
+}
+
+impl<'a, L: IncrementalLtHash> ElementWriter<'a, L> {
+    fn new(lthash: &'a mut L, remove: bool) -> Self {
+        let hasher = Some(lthash.element_hasher());
+        Self {
+            lthash,
+            hasher,
+            remove,
+        }
+    }
+}
+
+impl<L> AsyncWrite for ElementWriter<'_, L>
+where
+    L: IncrementalLtHash,
+    L::Hasher: Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match &mut self.get_mut().hasher {
+            Some(hasher) => {
+                hasher.update(buf);
+                Poll::Ready(Ok(buf.len()))
+            }
+            None => Poll::Ready(Err(closed())),
+        }
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Some(hasher) = this.hasher.take() {
+            if this.remove {
+                this.lthash.remove_hashed(hasher);
+            } else {
+                this.lthash.insert_hashed(hasher);
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn closed() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "the element writer is closed")
+}
@@ -0,0 +1,107 @@
+use crate::LtHash;
+
+/// Transactions on a checksum, implemented for every [`LtHash`].
+///
+/// # Examples
+/// ```
+/// # use lthash_rs::{LtHash, LtHash16, Transactional};
+/// # use sha3::Shake128;
+/// # use std::iter::FromIterator;
+/// let mut lthash = LtHash16::<Shake128>::from_iter(["apple"]);
+///
+/// let mut transaction = lthash.begin();
+/// transaction.insert("banana");
+/// {
+///     let mut savepoint = transaction.savepoint();
+///     savepoint.remove("apple");
+///     // dropped without committing: the removal is rolled back
+/// }
+/// transaction.commit();
+///
+/// assert_eq!(lthash, LtHash16::from_iter(["apple", "banana"]));
+/// ```
+pub trait Transactional: LtHash + Sized {
+    /// Starts a transaction, applied to `self` only once committed.
+    fn begin(&mut self) -> Transaction<'_, Self> {
+        Transaction::new(self)
+    }
+}
+
+impl<L: LtHash> Transactional for L {}
+
+/// A transaction on a checksum, accumulating its changes in a delta.
+///
+/// [`commit`](Self::commit) adds the delta to the checksum with
+/// [`union`](LtHash::union), while dropping the transaction (or calling
+/// [`rollback`](Self::rollback)) discards it.
+#[must_use = "the changes are rolled back unless the transaction is committed"]
+pub struct Transaction<'a, L> {
+    target: &'a mut L,
+    delta: L,
+}
+
+impl<'a, L: LtHash> Transaction<'a, L> {
+    fn new(target: &'a mut L) -> Self {
+        let delta = target.zero();
+        Self { target, delta }
+    }
+
+    /// Returns the changes of the transaction.
+    #[inline(always)]
+    pub fn delta(&self) -> &L {
+        &self.delta
+    }
+
+    /// Returns the checksum as it would be once the transaction is committed,
+    /// which for a [savepoint](Self::savepoint) is the delta of the enclosing
+    /// transaction.
+    pub fn current(&self) -> L {
+        self.target.union(&self.delta)
+    }
+
+    /// Inserts an element to the delta.
+    pub fn insert(&mut self, element: impl AsRef<[u8]>) {
+        self.delta.insert(element);
+    }
+
+    /// Removes an element from the delta.
+    pub fn remove(&mut self, element: impl AsRef<[u8]>) {
+        self.delta.remove(element);
+    }
+
+    /// Adds `rhs` to the delta.
+    pub fn union(&mut self, rhs: &L) {
+        self.delta = self.delta.union(rhs);
+    }
+
+    /// Subtracts `rhs` from the delta.
+    pub fn difference(&mut self, rhs: &L) {
+        self.delta = self.delta.difference(rhs);
+    }
+
+    /// Starts a nested transaction, whose changes are added to the delta of
+    /// `self` once committed.
+    pub fn savepoint(&mut self) -> Transaction<'_, L> {
+        Transaction::new(&mut self.delta)
+    }
+
+    /// Applies the delta to the checksum.
+    pub fn commit(self) {
+        *self.target = self.target.union(&self.delta);
+    }
+
+    /// Discards the delta, like dropping the transaction.
+    pub fn rollback(self) {}
+}
+
+impl<A, L> Extend<A> for Transaction<'_, L>
+where
+    A: AsRef<[u8]>,
+    L: LtHash,
+{
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
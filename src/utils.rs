@@ -57,15 +57,65 @@ pub fn read_u32(buf: &[u8]) -> u32 {
     }
 }
 
+pub struct HexDisplayRef64<'a>(pub &'a [u64]);
+
+impl fmt::Display for HexDisplayRef64<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for x in self.0 {
+            // we need to swap here to *display* LE order
+            write!(f, "{:016x}", x.swap_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HexDisplayRef64<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+/// Allocates a zeroed checksum directly on the heap, without building it on
+/// the stack first.
+pub(crate) fn zeroed<T: PrimInt, const N: usize>() -> Box<[T; N]> {
+    vec![T::zero(); N]
+        .into_boxed_slice()
+        .try_into()
+        .unwrap_or_else(|_| unreachable!())
+}
+
 pub(crate) fn into_bytes<T: bytemuck::Pod + PrimInt>(
-    mut checksum: [T; 1024],
+    checksum: &[T],
 ) -> Vec<u8> {
+    let mut bytes = bytemuck::cast_slice(checksum).to_vec();
+
     // pessimization for big endian platforms, byte swapping is required because the words are currently in big endian order and need to be reversed.
     if cfg!(target_endian = "big") {
-        for elem in &mut checksum {
-            *elem = elem.swap_bytes();
+        for word in bytes.chunks_exact_mut(std::mem::size_of::<T>()) {
+            word.reverse();
         }
     }
 
-    bytemuck::bytes_of(&checksum).to_vec()
+    bytes
+}
+
+/// Wipes a secret intermediate, like the output of the hash function, with the
+/// `zeroize` feature.
+#[inline(always)]
+pub(crate) fn wipe(bytes: &mut [u8]) {
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(bytes);
+    #[cfg(not(feature = "zeroize"))]
+    let _ = bytes;
+}
+
+/// Zeroes `bytes` unless `choice` is set, in constant time.
+pub(crate) fn mask(bytes: &mut [u8], choice: subtle::Choice) {
+    let mask = subtle::ConditionallySelectable::conditional_select(
+        &0u8, &0xff, choice,
+    );
+    for byte in bytes {
+        *byte &= mask;
+    }
 }
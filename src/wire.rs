@@ -0,0 +1,155 @@
+//! Framed wire format of the checksums, produced by `encode` and read by
+//! `decode`.
+//!
+//! All the integers are little-endian:
+//!
+//! | Length | Content                                                  |
+//! | ------ | -------------------------------------------------------- |
+//! | 4      | magic `LTHF`                                             |
+//! | 1      | format version (1)                                       |
+//! | 1      | bits per chunk (16, 20 or 32)                            |
+//! | 2      | number of chunks                                         |
+//! | 2      | id of the hash function, see [`XofId`]                   |
+//! | 1      | flags, bit 0 set if the element count is present         |
+//! | 1      | reserved (0)                                             |
+//! | 0 or 8 | number of elements                                       |
+//! |        | the checksum, as returned by `into_bytes`                |
+//! | 4      | CRC-32 of all the previous bytes                         |
+
+const MAGIC: &[u8; 4] = b"LTHF";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 12;
+const ELEMENT_COUNT: u8 = 1;
+
+/// Identifies the hash function in the wire format, so that checksums
+/// computed with another one are rejected.
+///
+/// Only the function is identified, not its key or customization string: a
+/// keyed [`Blake2xb`](crate::Blake2xb) has the same id as an unkeyed one, and
+/// a cSHAKE has the same id whatever its customization, so a checksum decoded
+/// with the wrong key or customization is accepted but will never match.
+/// Applications telling them apart must frame the key or customization (or a
+/// fingerprint of it) themselves.
+///
+/// The ids from `0x8000` are reserved to the applications implementing this
+/// trait for their own hash functions.
+pub trait XofId {
+    /// The id of the hash function.
+    const XOF_ID: u16;
+}
+
+impl XofId for crate::Blake2xb {
+    const XOF_ID: u16 = 3;
+}
+
+#[cfg(feature = "sha3")]
+impl XofId for sha3::Shake128 {
+    const XOF_ID: u16 = 1;
+}
+
+#[cfg(feature = "sha3")]
+impl XofId for sha3::Shake256 {
+    const XOF_ID: u16 = 2;
+}
+
+#[cfg(feature = "sha3")]
+impl XofId for sha3::CShake128 {
+    const XOF_ID: u16 = 6;
+}
+
+#[cfg(feature = "sha3")]
+impl XofId for sha3::CShake256 {
+    const XOF_ID: u16 = 7;
+}
+
+#[cfg(feature = "blake3")]
+impl XofId for blake3::Hasher {
+    const XOF_ID: u16 = 4;
+}
+
+#[cfg(feature = "k12")]
+impl XofId for k12::KangarooTwelve<'_> {
+    const XOF_ID: u16 = 5;
+}
+
+/// The kind of checksum held by a frame.
+pub(crate) struct FrameKind {
+    pub(crate) bits: u8,
+    pub(crate) chunks: u16,
+    pub(crate) xof_id: u16,
+}
+
+pub(crate) fn encode(
+    kind: FrameKind,
+    elements: Option<u64>,
+    checksum: &[u8],
+) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(HEADER_LEN + 8 + checksum.len() + 4);
+    frame.extend_from_slice(MAGIC);
+    frame.push(VERSION);
+    frame.push(kind.bits);
+    frame.extend_from_slice(&kind.chunks.to_le_bytes());
+    frame.extend_from_slice(&kind.xof_id.to_le_bytes());
+    match elements {
+        Some(elements) => {
+            frame.extend_from_slice(&[ELEMENT_COUNT, 0]);
+            frame.extend_from_slice(&elements.to_le_bytes());
+        }
+        None => frame.extend_from_slice(&[0, 0]),
+    }
+    frame.extend_from_slice(checksum);
+
+    let crc = crc32fast::hash(&frame);
+    frame.extend_from_slice(&crc.to_le_bytes());
+    frame
+}
+
+/// Checks the frame, returning the checksum and the number of elements.
+pub(crate) fn decode(
+    kind: FrameKind,
+    frame: &[u8],
+) -> Result<(&[u8], Option<u64>), String> {
+    if frame.len() < HEADER_LEN + 4 {
+        return Err(String::from("Truncated frame."));
+    }
+
+    let (frame, crc) = frame.split_at(frame.len() - 4);
+    if crc32fast::hash(frame).to_le_bytes() != crc {
+        return Err(String::from("Wrong CRC."));
+    }
+
+    let (header, body) = frame.split_at(HEADER_LEN);
+    if &header[..4] != MAGIC {
+        return Err(String::from("Wrong magic number."));
+    }
+    if header[4] != VERSION {
+        return Err(format!("Unsupported format version {}.", header[4]));
+    }
+    let chunks = u16::from_le_bytes([header[6], header[7]]);
+    if header[5] != kind.bits || chunks != kind.chunks {
+        return Err(format!(
+            "Expected {} chunks of {} bits, found {chunks} chunks of {} bits.",
+            kind.chunks, kind.bits, header[5],
+        ));
+    }
+    let xof_id = u16::from_le_bytes([header[8], header[9]]);
+    if xof_id != kind.xof_id {
+        return Err(format!(
+            "Expected hash function {}, found {xof_id}.",
+            kind.xof_id
+        ));
+    }
+    if header[10] & !ELEMENT_COUNT != 0 || header[11] != 0 {
+        return Err(String::from("Unknown flags."));
+    }
+
+    if header[10] & ELEMENT_COUNT == 0 {
+        return Ok((body, None));
+    }
+    if body.len() < 8 {
+        return Err(String::from("Truncated frame."));
+    }
+    let (elements, checksum) = body.split_at(8);
+    let elements = u64::from_le_bytes(elements.try_into().unwrap());
+    Ok((checksum, Some(elements)))
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use lthash_rs::{
+    dynamic::{Variant, Xof},
+    Algorithm, LtHash16Blake2xb, LtHash20Blake2xb, LtHash32Blake2xb,
+};
+
+#[test]
+fn identifiers() {
+    let cases = [
+        ("lthash16-blake2xb", Algorithm::LtHash16(Xof::Blake2xb)),
+        ("lthash16-shake128", Algorithm::LtHash16(Xof::Shake128)),
+        ("lthash20-blake3", Algorithm::LtHash20(Xof::Blake3)),
+        ("lthash32-shake256", Algorithm::LtHash32(Xof::Shake256)),
+        ("lthash32-k12", Algorithm::LtHash32(Xof::K12)),
+    ];
+    for (id, algorithm) in cases {
+        assert_eq!(id.parse::<Algorithm>(), Ok(algorithm));
+        assert_eq!(algorithm.to_string(), id);
+    }
+}
+
+#[test]
+fn round_trip() {
+    let ids: HashSet<_> = Algorithm::all().map(|a| a.to_string()).collect();
+    assert_eq!(ids.len(), Variant::ALL.len() * Xof::ALL.len());
+
+    for algorithm in Algorithm::all() {
+        assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+        assert_eq!(
+            Algorithm::new(algorithm.variant(), algorithm.xof()),
+            algorithm
+        );
+    }
+}
+
+#[test]
+fn unknown() {
+    for id in ["", "lthash16", "lthash64-shake128", "LtHash16-shake128"] {
+        assert!(id.parse::<Algorithm>().is_err());
+    }
+}
+
+#[test]
+fn concrete_types() {
+    assert_eq!(
+        LtHash16Blake2xb::algorithm(),
+        Some(Algorithm::LtHash16(Xof::Blake2xb))
+    );
+    assert_eq!(
+        LtHash20Blake2xb::algorithm(),
+        Some(Algorithm::LtHash20(Xof::Blake2xb))
+    );
+    assert_eq!(
+        LtHash32Blake2xb::algorithm(),
+        Some(Algorithm::LtHash32(Xof::Blake2xb))
+    );
+    assert!(format!("{:?}", LtHash20Blake2xb::new()).starts_with("LtHash20 "));
+}
+
+#[cfg(feature = "sha3")]
+#[test]
+fn shake() {
+    use lthash_rs::{LtHash16Shake128, LtHash32Shake256};
+
+    assert_eq!(
+        LtHash16Shake128::algorithm().unwrap().to_string(),
+        "lthash16-shake128"
+    );
+    assert_eq!(
+        LtHash32Shake256::algorithm().unwrap().to_string(),
+        "lthash32-shake256"
+    );
+}
+
+#[test]
+fn new_dyn() {
+    let algorithm: Algorithm = "lthash20-blake2xb".parse().unwrap();
+    let lthash = algorithm.new_dyn().unwrap();
+    assert_eq!(lthash.byte_len(), 2688);
+    assert!(lthash.as_any().is::<LtHash20Blake2xb>());
+}
+
+#[test]
+fn xof_ids() {
+    assert_eq!(Xof::Blake2xb.xof_id(), Some(3));
+    assert_eq!(LtHash16Blake2xb::algorithm().unwrap().xof(), Xof::Blake2xb);
+
+    let ids: HashSet<_> = Xof::ALL.iter().filter_map(|x| x.xof_id()).collect();
+    assert_eq!(
+        ids.len(),
+        Xof::ALL.iter().filter(|x| x.xof_id().is_some()).count()
+    );
+    #[cfg(feature = "sha3")]
+    assert_eq!(Xof::Shake256.xof_id(), Some(2));
+    #[cfg(not(feature = "k12"))]
+    assert_eq!(Xof::K12.xof_id(), None);
+}
@@ -0,0 +1,151 @@
+use digest::{Update, XofReader};
+use lthash_rs::{Blake2xb, Expander, LtHash, LtHash16, LtHash16Blake2xb};
+
+const PARAMS: &str = include_str!("./test-data/folly/blake2xb-params.txt");
+const REFERENCE: &str = include_str!("./test-data/blake2/blake2xb-ref.txt");
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    if hex == "-" || hex.is_empty() {
+        return Vec::new();
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn params_vectors() {
+    let input: Vec<u8> = (0..=255).collect();
+
+    for line in PARAMS.lines() {
+        let fields: Vec<_> = line.split(' ').collect();
+        let [key, salt, personalization] =
+            [fields[0], fields[1], fields[2]].map(decode_hex);
+        let output_len: u32 = fields[3].parse().unwrap();
+        let len: usize = fields[4].parse().unwrap();
+        let expected = decode_hex(fields[5]);
+
+        let mut hasher =
+            Blake2xb::new(Some(output_len), &key, &salt, &personalization)
+                .unwrap();
+        // split the input to hash it incrementally
+        hasher.update(&input[..100]);
+        hasher.update(&input[100..]);
+
+        let mut output = vec![0; len];
+        let mut reader = hasher.finalize_xof();
+        reader.read(&mut output[..len / 2]);
+        reader.read(&mut output[len / 2..]);
+        assert_eq!(output, expected, "{line}");
+    }
+}
+
+/// Checks the vectors written by the BLAKE2 reference code, in the format of
+/// its known-answer tests, with both a declared and a deferred output length.
+#[test]
+fn reference_vectors() {
+    let mut keyed = 0;
+    for entry in REFERENCE.split("\n\n").filter(|entry| !entry.is_empty()) {
+        let fields: Vec<_> = entry
+            .lines()
+            .map(|line| line.split_once('\t').unwrap().1)
+            .collect();
+        let [input, key, expected] =
+            [fields[0], fields[1], fields[2]].map(decode_hex);
+        keyed += usize::from(!key.is_empty());
+
+        let mut declared =
+            Blake2xb::new(Some(expected.len() as u32), &key, b"", b"").unwrap();
+        declared.update(&input);
+        let mut output = vec![0; expected.len()];
+        declared.finalize_xof().read(&mut output);
+        assert_eq!(output, expected, "{entry}");
+
+        let deferred = Blake2xb::new(None, &key, b"", b"").unwrap();
+        let mut output = vec![0; expected.len()];
+        deferred.expand(&input, &mut output);
+        assert_eq!(output, expected, "{entry}");
+    }
+    assert!(keyed > 0);
+}
+
+#[test]
+fn deferred_output_len() {
+    let key = b"key";
+    let deferred = Blake2xb::new(None, key, b"", b"").unwrap();
+    let declared = Blake2xb::new(Some(300), key, b"", b"").unwrap();
+
+    let mut left = [0; 300];
+    let mut right = [0; 300];
+    deferred.expand(b"hello", &mut left);
+    declared.expand(b"hello", &mut right);
+    assert_eq!(left, right);
+}
+
+/// Without an output length, `finalize_xof` produces an output of unknown
+/// length, unlike `expand`.
+#[test]
+fn unknown_output_len_expand() {
+    let mut hasher = Blake2xb::default();
+    hasher.update(b"hello");
+    let mut unknown = [0; 300];
+    hasher.finalize_xof().read(&mut unknown);
+
+    let mut expanded = [0; 300];
+    Blake2xb::default().expand(b"hello", &mut expanded);
+    assert_ne!(unknown, expanded);
+
+    let mut hasher = Blake2xb::new(Some(300), b"", b"", b"").unwrap();
+    hasher.update(b"hello");
+    let mut declared = [0; 300];
+    hasher.finalize_xof().read(&mut declared);
+    assert_eq!(declared, expanded);
+}
+
+#[test]
+fn preset_output_len() {
+    let hasher = Blake2xb::new(Some(2048), b"", b"", b"").unwrap();
+    let mut lthash = LtHash16::with_hasher(hasher);
+    lthash.insert("hello");
+    assert_eq!(lthash, LtHash16Blake2xb::from_iter(["hello"]));
+}
+
+#[test]
+#[should_panic(expected = "not the size of the checksum")]
+fn mismatched_output_len() {
+    let hasher = Blake2xb::new(Some(100), b"", b"", b"").unwrap();
+    LtHash16::with_hasher(hasher).insert("hello");
+}
+
+#[test]
+fn unknown_output_len() {
+    let mut unknown = Blake2xb::default();
+    let mut declared =
+        Blake2xb::new(Some(Blake2xb::UNKNOWN_OUTPUT_LEN), b"", b"", b"")
+            .unwrap();
+    unknown.update(b"hello");
+    declared.update(b"hello");
+
+    let mut left = [0; 300];
+    let mut right = [0; 300];
+    unknown.finalize_xof().read(&mut left);
+    declared.finalize_xof().read(&mut right);
+    assert_eq!(left, right);
+}
+
+#[test]
+#[should_panic(expected = "BLAKE2xb output exhausted")]
+fn read_past_output_len() {
+    let hasher = Blake2xb::new(Some(100), b"", b"", b"").unwrap();
+    hasher.finalize_xof().read(&mut [0; 101]);
+}
+
+#[test]
+fn invalid_params() {
+    assert!(Blake2xb::new(None, &[0; 65], b"", b"").is_err());
+    assert!(Blake2xb::new(None, b"", &[0; 17], b"").is_err());
+    assert!(Blake2xb::new(None, b"", b"", &[0; 17]).is_err());
+    assert!(Blake2xb::new(Some(0), b"", b"", b"").is_err());
+}
@@ -0,0 +1,146 @@
+use lthash_rs::{ConcurrentLtHash, LtHash, Transactional};
+use sha3::Shake128;
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type CheckedLtHash16 = lthash_rs::CheckedLtHash<LtHash16>;
+
+#[test]
+fn insert_remove() {
+    let mut lthash = CheckedLtHash16::default();
+    lthash.extend(["apple", "banana", "apple"]);
+    assert_eq!(lthash.count("apple"), 2);
+    assert_eq!(lthash.len(), 3);
+
+    lthash.remove("apple");
+    lthash.remove("banana");
+    assert_eq!(lthash.count("apple"), 1);
+    assert_eq!(lthash.count("banana"), 0);
+    assert_eq!(lthash.lthash(), &LtHash16::from_iter(["apple"]));
+    assert_eq!(lthash, CheckedLtHash16::from_iter(["apple"]));
+}
+
+#[test]
+fn try_remove_missing_element() {
+    let mut lthash = CheckedLtHash16::from_iter(["apple"]);
+
+    assert!(lthash.try_remove("banana").is_err());
+    assert!(lthash.try_remove("apple").is_ok());
+    assert!(lthash.try_remove("apple").is_err());
+    assert!(lthash.is_empty());
+    assert_eq!(lthash.into_inner(), LtHash16::new());
+}
+
+#[test]
+#[should_panic(
+    expected = "Removing an element 1 times while it was inserted 0 times."
+)]
+fn remove_missing_element() {
+    let mut lthash = CheckedLtHash16::default();
+    assert_eq!(
+        lthash.try_remove("apple").unwrap_err(),
+        "Removing an element 1 times while it was inserted 0 times."
+    );
+
+    lthash.remove("apple");
+}
+
+#[test]
+fn remove_from_delta() {
+    let mut delta = CheckedLtHash16::default().zero();
+    assert!(delta.is_delta());
+    delta.remove("apple");
+    assert_eq!(delta.count("apple"), -1);
+    assert_eq!(
+        delta.validate().unwrap_err(),
+        "An element was removed 1 more times than inserted."
+    );
+
+    delta.insert("apple");
+    assert!(delta.is_empty());
+    assert_eq!(delta.validate(), Ok(()));
+
+    let mut delta = CheckedLtHash16::default().into_delta();
+    delta.remove("apple");
+    assert_eq!(delta.count("apple"), -1);
+}
+
+#[test]
+fn equality_ignores_counts() {
+    let mut left = CheckedLtHash16::default();
+    left.insert("apple");
+    left.remove("apple");
+    let right = CheckedLtHash16::from_bytes(&left.clone().into_bytes());
+    assert_eq!(right.unwrap(), left);
+
+    let union = &left | &CheckedLtHash16::default().zero();
+    assert!(!union.is_delta());
+    assert!(union.is_zero());
+}
+
+#[test]
+fn union_difference() {
+    let left = CheckedLtHash16::from_iter(["apple", "banana"]);
+    let right = CheckedLtHash16::from_iter(["banana"]);
+
+    let union = &left | &right;
+    assert_eq!(union.count("banana"), 2);
+    assert_eq!(union.difference(&right), left);
+
+    assert!(right.try_difference(&left).is_err());
+    assert_eq!(left.try_difference(&right).unwrap().len(), 1);
+}
+
+#[test]
+fn difference_of_missing_elements() {
+    let left = CheckedLtHash16::from_iter(["apple"]);
+    let right = CheckedLtHash16::from_iter(["banana"]);
+    assert!(left.try_difference(&right).is_err());
+
+    let difference = left - right;
+    assert_eq!(difference.count("banana"), -1);
+    assert!(difference.validate().is_err());
+}
+
+#[test]
+fn transaction() {
+    let mut lthash = CheckedLtHash16::from_iter(["apple"]);
+
+    let mut transaction = lthash.begin();
+    transaction.remove("apple");
+    transaction.insert("banana");
+    assert!(transaction.delta().validate().is_err());
+    transaction.commit();
+
+    assert_eq!(lthash, CheckedLtHash16::from_iter(["banana"]));
+    assert_eq!(lthash.validate(), Ok(()));
+
+    let mut transaction = lthash.begin();
+    transaction.remove("apple");
+    transaction.commit();
+    assert!(lthash.validate().is_err());
+}
+
+#[test]
+fn concurrent() {
+    let lthash =
+        ConcurrentLtHash::new(CheckedLtHash16::from_iter(["apple", "banana"]));
+    lthash.remove("apple");
+    lthash.local().remove("banana");
+
+    let lthash = lthash.into_inner();
+    assert!(lthash.is_empty());
+    assert_eq!(lthash.validate(), Ok(()));
+}
+
+#[test]
+fn generic_call_sites() {
+    fn apply<L: LtHash + Default>() -> Vec<u8> {
+        let mut lthash = L::default();
+        lthash.insert("apple");
+        lthash.insert("banana");
+        lthash.remove("apple");
+        lthash.into_bytes()
+    }
+
+    assert_eq!(apply::<CheckedLtHash16>(), apply::<LtHash16>());
+}
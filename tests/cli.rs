@@ -0,0 +1,199 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+use tempfile::TempDir;
+
+const LORUM: &str = include_str!("./test-data/lorum-ipsum.txt");
+const HASH16: &str = include_str!("./test-data/lorum-hash-16.txt");
+const HASH32: &str = include_str!("./test-data/lorum-hash-32.txt");
+
+fn lthash(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lthash"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap().trim().to_owned()
+}
+
+/// Writes `content` to a file of `dir`, removed with it.
+fn temp_file(dir: &TempDir, name: &str, content: impl AsRef<[u8]>) -> PathBuf {
+    let path = dir.path().join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn hash_stdin_lines() {
+    let output = lthash(&["hash"], LORUM.as_bytes());
+    assert_eq!(stdout(output), HASH16.trim());
+
+    let output = lthash(&["--width", "32", "hash"], LORUM.as_bytes());
+    assert_eq!(stdout(output), HASH32.trim());
+
+    let output = lthash(&["hash", "--width", "32"], LORUM.as_bytes());
+    assert_eq!(stdout(output), HASH32.trim());
+}
+
+#[test]
+fn hash_lthash20() {
+    let output = lthash(&["--width", "20", "hash"], b"hello\nworld");
+    let expected =
+        lthash_rs::LtHash20::<sha3::Shake128>::from_iter(["hello", "world"]);
+    assert_eq!(stdout(output), lthash_rs::LtHash::to_hex_string(&expected));
+}
+
+#[test]
+fn hash_file_lines() {
+    let output =
+        lthash(&["hash", "--lines", "tests/test-data/lorum-ipsum.txt"], b"");
+    assert_eq!(stdout(output), HASH16.trim());
+}
+
+fn hash_binary(stdin: &[u8]) -> Vec<u8> {
+    let output = lthash(&["hash", "--binary"], stdin);
+    assert!(output.status.success(), "{output:?}");
+    output.stdout
+}
+
+#[test]
+fn binary() {
+    let dir = TempDir::new().unwrap();
+    let both = hash_binary(b"hello\nworld\n");
+    assert_eq!(both.len(), 2048);
+    let both = temp_file(&dir, "both", both);
+    let same = temp_file(&dir, "same", hash_binary(b"world\nhello\n"));
+    let world = temp_file(&dir, "world", hash_binary(b"world\n"));
+    let [both, same, world] =
+        [&both, &same, &world].map(|path| path.to_str().unwrap());
+
+    // the checksums are read as hex strings unless told otherwise
+    assert_eq!(lthash(&["eq", both, same], b"").status.code(), Some(2));
+
+    let output = lthash(&["--binary-input", "eq", both, same], b"");
+    assert!(output.status.success());
+    let output = lthash(&["eq", "--binary-input", both, world], b"");
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = lthash(&["--binary-input", "diff", both, world], b"");
+    assert_eq!(stdout(output), stdout(lthash(&["hash"], b"hello")));
+}
+
+#[test]
+fn add_remove() {
+    let dir = TempDir::new().unwrap();
+    let hello =
+        temp_file(&dir, "add-hello", stdout(lthash(&["hash"], b"hello")));
+    let output = lthash(&["add", hello.to_str().unwrap(), "world"], b"");
+    assert_eq!(stdout(output), stdout(lthash(&["hash"], b"hello\nworld")));
+
+    let output = lthash(&["remove", hello.to_str().unwrap()], b"hello\n");
+    assert_eq!(stdout(output), stdout(lthash(&["hash"], b"")));
+}
+
+#[test]
+fn union_diff_eq() {
+    let dir = TempDir::new().unwrap();
+    let hello =
+        temp_file(&dir, "union-hello", stdout(lthash(&["hash"], b"hello")));
+    let world =
+        temp_file(&dir, "union-world", stdout(lthash(&["hash"], b"world")));
+    let both = temp_file(
+        &dir,
+        "union-both",
+        stdout(lthash(&["hash"], b"hello\nworld")),
+    );
+    let [hello, world, both] =
+        [&hello, &world, &both].map(|path| path.to_str().unwrap());
+
+    let output = lthash(&["union", hello, world], b"");
+    assert_eq!(stdout(output), std::fs::read_to_string(both).unwrap());
+
+    let output = lthash(&["diff", both, world], b"");
+    assert_eq!(stdout(output), std::fs::read_to_string(hello).unwrap());
+
+    assert!(lthash(&["eq", hello, hello], b"").status.success());
+    assert_eq!(lthash(&["eq", hello, world], b"").status.code(), Some(1));
+}
+
+#[test]
+fn fingerprint() {
+    let dir = TempDir::new().unwrap();
+    let hello =
+        temp_file(&dir, "fingerprint", stdout(lthash(&["hash"], b"hello")));
+    let output = lthash(&["fingerprint", hello.to_str().unwrap()], b"");
+    let fingerprint = stdout(output);
+    assert_eq!(fingerprint.len(), 64);
+
+    let output = lthash(
+        &["--xof", "shake256", "fingerprint", hello.to_str().unwrap()],
+        b"",
+    );
+    assert_ne!(stdout(output), fingerprint);
+}
+
+#[test]
+fn invalid_checksum() {
+    let dir = TempDir::new().unwrap();
+    let invalid = temp_file(&dir, "invalid", "not a checksum");
+    let output = lthash(&["fingerprint", invalid.to_str().unwrap()], b"");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn hash_dir() {
+    let dir = TempDir::new().unwrap();
+    let nested = dir.path().join("nested");
+    std::fs::create_dir(&nested).unwrap();
+    temp_file(&dir, "hello", "hello");
+    std::fs::write(nested.join("world"), "world").unwrap();
+
+    let output = lthash(&["hash", "--dir", dir.path().to_str().unwrap()], b"");
+    assert_eq!(stdout(output), stdout(lthash(&["hash"], b"hello\nworld")));
+}
+
+#[cfg(unix)]
+#[test]
+fn hash_dir_symlink_loop() {
+    let dir = TempDir::new().unwrap();
+    temp_file(&dir, "hello", "hello");
+    std::os::unix::fs::symlink(dir.path(), dir.path().join("loop")).unwrap();
+
+    let output = lthash(&["hash", "--dir", dir.path().to_str().unwrap()], b"");
+    assert_eq!(stdout(output), stdout(lthash(&["hash"], b"hello")));
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+fn hash_blake3() {
+    const HASH: &str = include_str!("./test-data/lorum-hash-32-blake3.txt");
+
+    let output = lthash(
+        &["--width", "32", "--xof", "blake3", "hash"],
+        LORUM.as_bytes(),
+    );
+    assert_eq!(stdout(output), HASH.trim());
+}
+
+#[cfg(not(feature = "blake3"))]
+#[test]
+fn disabled_xof() {
+    let output = lthash(&["--xof", "blake3", "hash"], b"");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("invalid value 'blake3'"), "{stderr}");
+}
@@ -0,0 +1,82 @@
+use digest::Update;
+use lthash_rs::LtHash;
+use sha3::Shake128;
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type LtHash20 = lthash_rs::LtHash20<Shake128>;
+type LtHash32 = lthash_rs::LtHash32<Shake128>;
+
+#[test]
+fn empty() {
+    let compressed = LtHash16::new().to_compressed_bytes();
+    assert_eq!(compressed, [0x80, 0x08, 0]);
+    assert_eq!(
+        LtHash16::from_compressed_bytes(&compressed).unwrap(),
+        LtHash16::new()
+    );
+}
+
+#[test]
+fn sparse() {
+    let mut bytes = vec![0; 4096];
+    bytes[4..8].copy_from_slice(&[1, 2, 3, 4]);
+    bytes[4092] = 5;
+    let lthash = LtHash32::try_from(&bytes[..]).unwrap();
+
+    let compressed = lthash.to_compressed_bytes();
+    assert_eq!(compressed, [1, 1, 1, 2, 3, 4, 0xfd, 0x07, 1, 5, 0, 0, 0]);
+    assert_eq!(
+        LtHash32::from_compressed_bytes(&compressed).unwrap(),
+        lthash
+    );
+}
+
+#[test]
+fn random() {
+    let lthash = LtHash16::from_iter(["apple", "banana"]);
+    let compressed = lthash.to_compressed_bytes();
+    assert!(compressed.len() <= 2048 + 8);
+    assert_eq!(
+        LtHash16::from_compressed_bytes(&compressed).unwrap(),
+        lthash
+    );
+
+    let lthash = LtHash20::from_iter(["apple", "banana"]);
+    let compressed = lthash.to_compressed_bytes();
+    assert_eq!(
+        LtHash20::from_compressed_bytes(&compressed).unwrap(),
+        lthash
+    );
+}
+
+#[test]
+fn invalid() {
+    // truncated
+    assert!(LtHash16::from_compressed_bytes(&[]).is_err());
+    assert!(LtHash16::from_compressed_bytes(&[0x80]).is_err());
+    assert!(LtHash16::from_compressed_bytes(&[0, 1, 0]).is_err());
+    // too many words
+    assert!(LtHash16::from_compressed_bytes(&[0x81, 0x08, 0]).is_err());
+    // empty run
+    assert!(LtHash16::from_compressed_bytes(&[0, 0]).is_err());
+    // trailing bytes
+    assert!(LtHash16::from_compressed_bytes(&[0x80, 0x08, 0, 0]).is_err());
+    // padding bits
+    let mut compressed = vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0x80];
+    compressed.extend([0xcf, 0x02, 0]);
+    assert!(LtHash20::from_compressed_bytes(&compressed).is_err());
+}
+
+#[test]
+fn keyed_hasher() {
+    let hasher = Shake128::default().chain(b"customization");
+    let mut lthash = LtHash32::with_hasher(hasher.clone());
+    lthash.insert("apple");
+    let compressed = lthash.to_compressed_bytes();
+
+    let mut decompressed =
+        LtHash32::from_compressed_bytes_with(&compressed, hasher).unwrap();
+    decompressed.insert("banana");
+    lthash.insert("banana");
+    assert_eq!(decompressed, lthash);
+}
@@ -0,0 +1,71 @@
+use std::thread;
+
+use lthash_rs::ConcurrentLtHash;
+use sha3::{CShake128, CShake128Core, Shake128};
+
+type LtHash32 = lthash_rs::LtHash32<Shake128>;
+
+fn elements(thread: usize) -> Vec<String> {
+    (0..100).map(|i| format!("{thread}-{i}")).collect()
+}
+
+#[test]
+fn threads() {
+    let lthash = ConcurrentLtHash::with_shards(LtHash32::new(), 3);
+    thread::scope(|scope| {
+        for i in 0..8 {
+            let lthash = &lthash;
+            scope.spawn(move || {
+                if i % 2 == 0 {
+                    elements(i).iter().for_each(|e| lthash.insert(e));
+                } else {
+                    lthash.local().extend(elements(i));
+                }
+            });
+        }
+    });
+
+    let expected = LtHash32::from_iter((0..8).flat_map(elements));
+    assert_eq!(lthash.snapshot(), expected);
+    assert_eq!(lthash.into_inner(), expected);
+}
+
+#[test]
+fn local_flush() {
+    let lthash = ConcurrentLtHash::new(LtHash32::from_iter(["apple"]));
+    assert!(lthash.shards() >= 1);
+
+    let mut local = lthash.local();
+    local.insert("banana");
+    local.remove("apple");
+    assert_eq!(lthash.snapshot(), LtHash32::from_iter(["apple"]));
+    local.flush();
+    assert_eq!(lthash.snapshot(), LtHash32::from_iter(["banana"]));
+
+    local.insert("kiwi");
+    drop(local);
+    assert_eq!(lthash.snapshot(), LtHash32::from_iter(["banana", "kiwi"]));
+}
+
+#[test]
+fn union_difference() {
+    let lthash = ConcurrentLtHash::with_shards(LtHash32::new(), 0);
+    assert_eq!(lthash.shards(), 1);
+
+    lthash.union(&LtHash32::from_iter(["apple", "banana"]));
+    lthash.difference(&LtHash32::from_iter(["apple"]));
+    lthash.remove("banana");
+    assert_eq!(lthash.snapshot(), LtHash32::new());
+}
+
+#[test]
+fn keeps_the_hasher() {
+    let hasher = CShake128::from_core(CShake128Core::new(b"customization"));
+    let mut expected = lthash_rs::LtHash32::with_hasher(hasher);
+    let lthash = ConcurrentLtHash::with_shards(expected.clone(), 2);
+    lthash.insert("apple");
+    lthash.local().insert("banana");
+    expected.extend(["apple", "banana"]);
+
+    assert_eq!(lthash.snapshot(), expected);
+}
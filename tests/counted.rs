@@ -0,0 +1,66 @@
+use lthash_rs::LtHash;
+use sha3::Shake128;
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type CountedLtHash16 = lthash_rs::CountedLtHash<LtHash16>;
+
+#[test]
+fn insert_remove() {
+    let mut lthash = CountedLtHash16::default();
+    lthash.extend(["apple", "banana", "kiwi"]);
+    lthash.remove("banana");
+
+    assert_eq!(lthash.count(), 2);
+    assert_eq!(lthash.lthash(), &LtHash16::from_iter(["apple", "kiwi"]));
+    assert_eq!(lthash, CountedLtHash16::from_iter(["apple", "kiwi"]));
+}
+
+#[test]
+fn remove_missing_element() {
+    let mut lthash = CountedLtHash16::default();
+    lthash.remove("apple");
+    assert_eq!(lthash.count(), -1);
+
+    lthash.reset();
+    assert_eq!(lthash, CountedLtHash16::default());
+}
+
+#[test]
+fn union_difference() {
+    let left = CountedLtHash16::from_iter(["apple", "banana"]);
+    let right = CountedLtHash16::from_iter(["kiwi"]);
+
+    let union = &left | &right;
+    assert_eq!(union.count(), 3);
+    assert_eq!(
+        union,
+        CountedLtHash16::from_iter(["apple", "banana", "kiwi"])
+    );
+    assert_eq!(union - right, left);
+}
+
+#[test]
+fn equality_includes_count() {
+    let mut lthash = LtHash16::new();
+    lthash.insert("apple");
+
+    assert_ne!(
+        CountedLtHash16::from_parts(lthash.clone(), 1),
+        CountedLtHash16::from_parts(lthash, 2)
+    );
+}
+
+#[test]
+fn to_from_bytes() {
+    let mut lthash = CountedLtHash16::from_iter(["apple"]);
+    lthash.remove("banana");
+    lthash.remove("kiwi");
+
+    let bytes = lthash.clone().into_bytes();
+    assert_eq!(bytes.len(), 2048 + 8);
+    assert_eq!(bytes[2048..], (-1i64).to_le_bytes());
+    assert_eq!(CountedLtHash16::try_from(&bytes[..]).unwrap(), lthash);
+
+    assert!(CountedLtHash16::try_from(&bytes[..2048]).is_err());
+    assert!(CountedLtHash16::try_from(&bytes[..4]).is_err());
+}
@@ -0,0 +1,89 @@
+use lthash_rs::{
+    dynamic::{lthash_from_config, DynLtHash, Variant, Xof},
+    LtHash, LtHash16Blake2xb, LtHash20Blake2xb, LtHash32Blake2xb,
+};
+
+fn inserted(variant: Variant, elements: &[&str]) -> Box<dyn DynLtHash> {
+    let mut lthash = lthash_from_config(variant, Xof::Blake2xb).unwrap();
+    for element in elements {
+        lthash.dyn_insert(element.as_bytes());
+    }
+    lthash
+}
+
+#[test]
+fn runtime_selection() {
+    let elements = ["apple", "banana"];
+
+    let lthash = inserted(Variant::LtHash16, &elements);
+    let expected = LtHash16Blake2xb::from_iter(elements);
+    assert_eq!(lthash.as_any().downcast_ref(), Some(&expected));
+    assert_eq!(lthash.byte_len(), 2048);
+
+    let lthash = inserted(Variant::LtHash20, &elements);
+    let expected = LtHash20Blake2xb::from_iter(elements);
+    assert_eq!(lthash.as_any().downcast_ref(), Some(&expected));
+
+    let lthash = inserted(Variant::LtHash32, &elements);
+    let expected = LtHash32Blake2xb::from_iter(elements);
+    assert_eq!(lthash.as_any().downcast_ref(), Some(&expected));
+}
+
+#[test]
+fn union_difference() {
+    let left = inserted(Variant::LtHash16, &["apple", "banana"]);
+    let right = inserted(Variant::LtHash16, &["banana"]);
+
+    let difference = left.dyn_difference(right.as_ref()).unwrap();
+    assert!(*difference == *inserted(Variant::LtHash16, &["apple"]));
+    let union = difference.dyn_union(right.as_ref()).unwrap();
+    assert!(*union == *left);
+
+    let other = inserted(Variant::LtHash32, &["banana"]);
+    assert!(left.dyn_union(other.as_ref()).is_err());
+    assert!(left.dyn_difference(other.as_ref()).is_err());
+    assert!(*left != *other);
+}
+
+#[test]
+fn bytes() {
+    let mut lthash = inserted(Variant::LtHash20, &["apple"]);
+    let bytes = lthash.to_bytes();
+    assert_eq!(bytes.len(), lthash.byte_len());
+    assert!(*lthash.dyn_with_bytes(&bytes).unwrap() == *lthash);
+
+    let copy = lthash.clone();
+    lthash.dyn_remove(b"apple");
+    assert!(lthash.dyn_is_zero());
+    assert!(!copy.dyn_is_zero());
+    assert!(*lthash == *copy.dyn_zero());
+    assert_eq!(
+        copy.dyn_to_hex_string(),
+        LtHash20Blake2xb::from_iter(["apple"]).to_hex_string()
+    );
+}
+
+#[test]
+fn disabled_features() {
+    for xof in [Xof::Shake128, Xof::Shake256, Xof::Blake3, Xof::K12] {
+        let lthash = lthash_from_config(Variant::LtHash32, xof);
+        let enabled = if matches!(xof, Xof::Shake128 | Xof::Shake256) {
+            cfg!(feature = "sha3")
+        } else if matches!(xof, Xof::Blake3) {
+            cfg!(feature = "blake3")
+        } else {
+            matches!(xof, Xof::K12) && cfg!(feature = "k12")
+        };
+        assert_eq!(lthash.is_ok(), enabled, "{xof:?}");
+    }
+}
+
+#[test]
+fn both_traits_in_scope() {
+    let mut lthash = LtHash16Blake2xb::new();
+    lthash.insert("apple");
+    lthash.dyn_insert(b"banana");
+    assert_eq!(lthash, LtHash16Blake2xb::from_iter(["apple", "banana"]));
+    assert!(!lthash.dyn_is_zero());
+    assert!(lthash.zero().is_zero());
+}
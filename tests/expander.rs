@@ -0,0 +1,128 @@
+use lthash_rs::{
+    Blake2xb, CounterMode, Expander, LtHash, LtHash16, LtHash20, LtHash32,
+    LtHashPair, Precomputed,
+};
+use sha2::{Digest, Sha224, Sha256};
+use sha3::Shake128;
+
+/// Builds the expansion of `CounterMode<D>` by hand.
+fn counter_mode<D: Digest>(element: &[u8], len: usize) -> Vec<u8> {
+    let mut output = Vec::new();
+    for counter in 0u32.. {
+        if output.len() >= len {
+            break;
+        }
+        let digest = D::new()
+            .chain_update(element)
+            .chain_update(counter.to_be_bytes());
+        output.extend_from_slice(&digest.finalize());
+    }
+    output.truncate(len);
+    output
+}
+
+#[test]
+fn xof() {
+    let mut output = [0u8; 2048];
+    Shake128::default().expand(b"hello", &mut output);
+
+    let mut precomputed = LtHash16::<Precomputed>::new();
+    precomputed.insert(output);
+    let mut lthash = LtHash16::<Shake128>::new();
+    lthash.insert("hello");
+    assert_eq!(lthash.into_bytes(), precomputed.into_bytes());
+}
+
+#[test]
+fn counter_mode_sha256() {
+    let mut lthash = LtHash32::<CounterMode<Sha256>>::new();
+    lthash.insert("hello");
+    lthash.insert("world");
+
+    let mut precomputed = LtHash32::<Precomputed>::new();
+    precomputed.insert(counter_mode::<Sha256>(b"hello", 4096));
+    precomputed.insert(counter_mode::<Sha256>(b"world", 4096));
+    assert_eq!(lthash.into_bytes(), precomputed.into_bytes());
+}
+
+#[test]
+fn counter_mode_truncated() {
+    // 2688 is not a multiple of the 28 bytes of SHA-224
+    let mut lthash = LtHash20::<CounterMode<Sha224>>::new();
+    lthash.insert("hello");
+
+    let mut precomputed = LtHash20::<Precomputed>::new();
+    precomputed.insert(counter_mode::<Sha224>(b"hello", 2688));
+    assert_eq!(lthash.into_bytes(), precomputed.into_bytes());
+}
+
+#[test]
+fn counter_mode_prefix() {
+    let prefixed = CounterMode::new(Sha256::new().chain_update("prefix"));
+    let mut lthash = LtHash16::with_hasher(prefixed);
+    lthash.insert("hello");
+
+    let mut precomputed = LtHash16::<Precomputed>::new();
+    precomputed.insert(counter_mode::<Sha256>(b"prefixhello", 2048));
+    assert_eq!(lthash.into_bytes(), precomputed.into_bytes());
+}
+
+#[test]
+fn precomputed() {
+    let hello = counter_mode::<Sha256>(b"hello", 2048);
+    let world = counter_mode::<Sha256>(b"world", 2048);
+
+    let mut lthash = LtHash16::<Precomputed>::new();
+    lthash.extend([&hello, &world]);
+    lthash.remove(&hello);
+
+    let mut expected = LtHash16::<Precomputed>::new();
+    expected.insert(&world);
+    assert_eq!(lthash, expected);
+}
+
+#[test]
+fn precomputed_digest() {
+    let digest = Sha256::digest("hello");
+    let mut lthash = LtHash16::<Precomputed>::new();
+    lthash.insert(digest);
+
+    let mut expected = LtHash16::<Blake2xb>::new();
+    expected.insert(digest);
+    assert_eq!(lthash.into_bytes(), expected.into_bytes());
+}
+
+#[test]
+#[should_panic(
+    expected = "The precomputed element has 4096 bytes instead of 2048."
+)]
+fn precomputed_wrong_len() {
+    let mut lthash = LtHash16::<Precomputed>::new();
+    lthash.insert(counter_mode::<Sha256>(b"hello", 4096));
+}
+
+#[test]
+fn counter_mode_pair() {
+    let pair = LtHashPair::<CounterMode<Sha256>>::from_iter(["hello"]);
+    let (lthash16, lthash32) = pair.into_parts();
+    assert_eq!(lthash16, LtHash16::from_iter(["hello"]));
+    assert_eq!(lthash32, LtHash32::from_iter(["hello"]));
+}
+
+#[cfg(feature = "aes")]
+#[test]
+fn aes_ctr() {
+    use aes::cipher::{KeyIvInit, StreamCipher};
+    use lthash_rs::AesCtr;
+
+    let mut lthash = LtHash32::<AesCtr<Sha256>>::new();
+    lthash.insert("hello");
+
+    let key = Sha256::digest("hello");
+    let mut keystream = vec![0u8; 4096];
+    ctr::Ctr128BE::<aes::Aes256>::new(&key, &Default::default())
+        .apply_keystream(&mut keystream);
+    let mut precomputed = LtHash32::<Precomputed>::new();
+    precomputed.insert(keystream);
+    assert_eq!(lthash.into_bytes(), precomputed.into_bytes());
+}
@@ -0,0 +1,141 @@
+//! Test vectors of `LtHash<16, 1024>`, `LtHash<20, 1008>` and
+//! `LtHash<32, 1024>` from `generate.py`, a Python port of folly's algorithm
+//! (not folly itself), see `test-data/folly/README.md` for their provenance.
+
+use digest::XofReader;
+use lthash_rs::{
+    Blake2xb, Expander, LtHash, LtHash16Blake2xb, LtHash20Blake2xb,
+    LtHash32Blake2xb,
+};
+
+const BLAKE2XB_2048: &str = include_str!("./test-data/folly/blake2xb-2048.txt");
+const BLAKE2XB_2688: &str = include_str!("./test-data/folly/blake2xb-2688.txt");
+const BLAKE2XB_4096: &str = include_str!("./test-data/folly/blake2xb-4096.txt");
+const LTHASH16: &str = include_str!("./test-data/folly/lthash16.txt");
+const LTHASH16_KEYED: &str =
+    include_str!("./test-data/folly/lthash16-keyed.txt");
+const LTHASH20: &str = include_str!("./test-data/folly/lthash20.txt");
+const LTHASH32: &str = include_str!("./test-data/folly/lthash32.txt");
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    if hex == "-" {
+        return Vec::new();
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Parses the lines of a test vector file as pairs of hex strings.
+fn vectors(file: &str) -> Vec<(&str, Vec<u8>)> {
+    file.lines()
+        .map(|line| {
+            let (input, output) = line.split_once(' ').unwrap();
+            (input, decode_hex(output))
+        })
+        .collect()
+}
+
+fn elements() -> Vec<Vec<u8>> {
+    vectors(BLAKE2XB_2048)
+        .into_iter()
+        .map(|(element, _)| decode_hex(element))
+        .collect()
+}
+
+/// Checks that the checksum of the first `n` elements matches the port.
+fn check<L>(checksums: &str)
+where
+    L: LtHash + Default,
+{
+    check_with(checksums, L::default)
+}
+
+fn check_with<L: LtHash>(checksums: &str, new: impl Fn() -> L) {
+    let elements = elements();
+
+    for (n, checksum) in vectors(checksums) {
+        let n: usize = n.parse().unwrap();
+        let mut lthash = new();
+        for element in &elements[..n] {
+            lthash.insert(element);
+        }
+
+        assert_eq!(lthash.into_bytes(), checksum, "{n} elements");
+    }
+}
+
+#[test]
+fn blake2xb() {
+    for (file, len) in [
+        (BLAKE2XB_2048, 2048),
+        (BLAKE2XB_2688, 2688),
+        (BLAKE2XB_4096, 4096),
+    ] {
+        for (input, expected) in vectors(file) {
+            let mut output = vec![0; len];
+            Blake2xb::default().expand(&decode_hex(input), &mut output);
+            assert_eq!(output, expected, "{input}");
+
+            let hasher = Blake2xb::new(Some(len as u32), b"", b"", b"");
+            let mut hasher = hasher.unwrap();
+            digest::Update::update(&mut hasher, &decode_hex(input));
+            hasher.finalize_xof().read(&mut output);
+            assert_eq!(output, expected, "{input}");
+        }
+    }
+}
+
+#[test]
+fn lthash16() {
+    check::<LtHash16Blake2xb>(LTHASH16);
+}
+
+#[test]
+fn lthash16_keyed() {
+    // the key given to `LtHash::setKey`
+    let key = b"folly-lthash-key-0123456789abcde";
+    let hasher = Blake2xb::new(None, key, b"", b"").unwrap();
+    check_with(LTHASH16_KEYED, || {
+        LtHash16Blake2xb::with_hasher(hasher.clone())
+    });
+}
+
+#[test]
+fn lthash20() {
+    check::<LtHash20Blake2xb>(LTHASH20);
+}
+
+#[test]
+fn lthash32() {
+    check::<LtHash32Blake2xb>(LTHASH32);
+}
+
+#[test]
+fn lthash20_remove() {
+    let elements = elements();
+    let checksums = vectors(LTHASH20);
+
+    let mut lthash = LtHash20Blake2xb::new();
+    lthash.extend(&elements);
+    for (n, element) in elements.iter().enumerate().rev() {
+        lthash.remove(element);
+        assert_eq!(lthash.to_hex_string(), hex(&checksums[n].1));
+    }
+}
+
+#[test]
+fn lthash20_padding_bits() {
+    let (_, mut checksum) = vectors(LTHASH20).pop().unwrap();
+    assert!(LtHash20Blake2xb::try_from(&checksum[..]).is_ok());
+
+    // the padding bit after the first chunk
+    checksum[2] |= 0x10;
+    assert!(LtHash20Blake2xb::try_from(&checksum[..]).is_err());
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
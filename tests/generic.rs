@@ -0,0 +1,88 @@
+use lthash_rs::{CheckedLtHash, CountedLtHash, Lanes, LtHash, MultiLtHash};
+use sha3::{CShake128, CShake128Core, Shake128};
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type LtHash20 = lthash_rs::LtHash20<Shake128>;
+type LtHash32 = lthash_rs::LtHash32<Shake128>;
+
+// only uses the trait
+fn verify<L: LtHash + Default>(elements: &[&str]) {
+    let mut lthash = L::default();
+    assert!(lthash.is_zero());
+    for element in elements {
+        lthash.insert(element);
+    }
+    assert!(!lthash.is_zero());
+    assert_eq!(lthash.zero(), L::default());
+
+    let bytes = lthash.clone().into_bytes();
+    assert_eq!(bytes.len(), L::BYTE_LEN);
+    assert_eq!(L::from_bytes(&bytes).as_ref(), Ok(&lthash));
+    assert_eq!(lthash.with_bytes(&bytes), Ok(lthash.clone()));
+    assert!(L::from_bytes(&bytes[1..]).is_err());
+
+    let copy = lthash.clone();
+    lthash.remove(elements[0]);
+    assert_ne!(lthash, copy);
+    assert!(format!("{lthash:?}").len() > 1);
+}
+
+#[test]
+fn generic_usage() {
+    let elements = ["apple", "banana", "kiwi"];
+    verify::<LtHash16>(&elements);
+    verify::<LtHash20>(&elements);
+    verify::<LtHash32>(&elements);
+    verify::<CountedLtHash<LtHash32>>(&elements);
+    #[cfg(feature = "sha3")]
+    verify::<lthash_rs::LtHashPair<sha3::Shake256>>(&elements);
+    verify::<MultiLtHash<(LtHash16, LtHash20, LtHash32)>>(&elements);
+}
+
+#[test]
+fn lanes() {
+    fn layout<L: Lanes>() -> (u32, usize, usize) {
+        (L::LANE_BITS, L::LANE_COUNT, L::BYTE_LEN)
+    }
+
+    assert_eq!(layout::<LtHash16>(), (16, 1024, 2048));
+    assert_eq!(layout::<LtHash20>(), (20, 1008, 2688));
+    assert_eq!(layout::<LtHash32>(), (32, 1024, 4096));
+}
+
+#[test]
+fn keeps_the_hasher() {
+    let hasher = CShake128::from_core(CShake128Core::new(b"customization"));
+    let mut lthash = lthash_rs::LtHash16::with_hasher(hasher);
+    lthash.insert("apple");
+    let bytes = lthash.clone().into_bytes();
+
+    let mut zero = lthash.zero();
+    assert!(zero.is_zero());
+    zero.insert("apple");
+    assert_eq!(zero, lthash);
+
+    let mut decoded = lthash.with_bytes(&bytes).unwrap();
+    decoded.remove("apple");
+    assert!(decoded.is_zero());
+}
+
+#[test]
+fn checked_bytes() {
+    let lthash = CheckedLtHash::<LtHash16>::from_iter(["apple"]);
+    let zero = lthash.zero();
+    assert_eq!(zero.count("apple"), 0);
+
+    // the elements cannot be recovered, so they are not checked
+    let bytes = lthash.clone().into_bytes();
+    let mut decoded = lthash.with_bytes(&bytes).unwrap();
+    assert_eq!(decoded.count("apple"), 0);
+    decoded.remove("apple");
+    assert!(decoded.is_zero());
+    assert_eq!(decoded.validate(), Ok(()));
+    assert!(CheckedLtHash::<LtHash16>::from_bytes(&bytes).is_ok());
+    assert_eq!(
+        lthash.with_bytes(&zero.clone().into_bytes()),
+        Ok(zero.clone())
+    );
+}
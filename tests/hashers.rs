@@ -0,0 +1,151 @@
+#![cfg(any(feature = "sha3", feature = "blake3", feature = "k12"))]
+
+use lthash_rs::LtHash;
+
+const LORUM: &str = include_str!("./test-data/lorum-ipsum.txt");
+
+/// Sums the lanes of the given extendable outputs, independently of LtHash.
+fn expected_hex(outputs: &[Vec<u8>], lane_bytes: usize) -> String {
+    let mut lanes = vec![0u32; 1024];
+    for output in outputs {
+        for (lane, bytes) in lanes.iter_mut().zip(output.chunks(lane_bytes)) {
+            let mut le = [0u8; 4];
+            le[..lane_bytes].copy_from_slice(bytes);
+            *lane = lane.wrapping_add(u32::from_le_bytes(le));
+        }
+    }
+
+    lanes
+        .iter()
+        .flat_map(|lane| lane.to_le_bytes()[..lane_bytes].to_vec())
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(feature = "sha3")]
+#[test]
+fn shake_aliases() {
+    use digest::ExtendableOutput;
+    use lthash_rs::{LtHash16Shake128, LtHash32Shake128, LtHash32Shake256};
+
+    const HASH16: &str = include_str!("./test-data/lorum-hash-16.txt");
+    const HASH32: &str = include_str!("./test-data/lorum-hash-32.txt");
+
+    let elements = ["hello", "world"];
+    let lthash: LtHash32Shake256 = elements.iter().collect();
+    let outputs: Vec<_> = elements
+        .iter()
+        .map(|element| {
+            let mut output = vec![0; 4096];
+            sha3::Shake256::digest_xof(element, &mut output);
+            output
+        })
+        .collect();
+    assert_eq!(lthash.to_hex_string(), expected_hex(&outputs, 4));
+
+    let lthash: LtHash16Shake128 = LORUM.lines().collect();
+    assert_eq!(lthash.to_hex_string(), HASH16.trim());
+
+    let lthash: LtHash32Shake128 = LORUM.lines().collect();
+    assert_eq!(lthash.to_hex_string(), HASH32.trim());
+}
+
+#[cfg(feature = "blake3")]
+mod blake3 {
+    use super::*;
+    use lthash_rs::{LtHash16Blake3, LtHash32Blake3};
+
+    const HASH16: &str = include_str!("./test-data/lorum-hash-16-blake3.txt");
+    const HASH32: &str = include_str!("./test-data/lorum-hash-32-blake3.txt");
+
+    fn xof(element: &str, len: usize) -> Vec<u8> {
+        let mut output = vec![0; len];
+        ::blake3::Hasher::new()
+            .update(element.as_bytes())
+            .finalize_xof()
+            .fill(&mut output);
+        output
+    }
+
+    #[test]
+    fn empty_input_vector() {
+        // from the official BLAKE3 test vectors
+        assert_eq!(
+            xof("", 32),
+            b"\xaf\x13\x49\xb9\xf5\xf9\xa1\xa6\xa0\x40\x4d\xea\x36\xdc\xc9\x49\
+              \x9b\xcb\x25\xc9\xad\xc1\x12\xb7\xcc\x9a\x93\xca\xe4\x1f\x32\x62"
+        );
+    }
+
+    #[test]
+    fn native_xof() {
+        let elements = ["hello", "world"];
+
+        let lthash: LtHash16Blake3 = elements.iter().collect();
+        let outputs: Vec<_> = elements.iter().map(|e| xof(e, 2048)).collect();
+        assert_eq!(lthash.to_hex_string(), expected_hex(&outputs, 2));
+
+        let lthash: LtHash32Blake3 = elements.iter().collect();
+        let outputs: Vec<_> = elements.iter().map(|e| xof(e, 4096)).collect();
+        assert_eq!(lthash.to_hex_string(), expected_hex(&outputs, 4));
+    }
+
+    #[test]
+    fn lorum_ipsum() {
+        let lthash: LtHash16Blake3 = LORUM.lines().collect();
+        assert_eq!(lthash.to_hex_string(), HASH16.trim());
+
+        let lthash: LtHash32Blake3 = LORUM.lines().collect();
+        assert_eq!(lthash.to_hex_string(), HASH32.trim());
+    }
+}
+
+#[cfg(feature = "k12")]
+mod k12 {
+    use super::*;
+    use digest::{ExtendableOutput, Update, XofReader};
+    use lthash_rs::{LtHash16K12, LtHash32K12};
+
+    const HASH16: &str = include_str!("./test-data/lorum-hash-16-k12.txt");
+    const HASH32: &str = include_str!("./test-data/lorum-hash-32-k12.txt");
+
+    fn xof(element: &str, len: usize) -> Vec<u8> {
+        let mut output = vec![0; len];
+        let mut hasher = ::k12::KangarooTwelve::default();
+        hasher.update(element.as_bytes());
+        hasher.finalize_xof().read(&mut output);
+        output
+    }
+
+    #[test]
+    fn empty_input_vector() {
+        // from the KangarooTwelve specification (RFC 9861)
+        assert_eq!(
+            xof("", 32),
+            b"\x1a\xc2\xd4\x50\xfc\x3b\x42\x05\xd1\x9d\xa7\xbf\xca\x1b\x37\x51\
+              \x3c\x08\x03\x57\x7a\xc7\x16\x7f\x06\xfe\x2c\xe1\xf0\xef\x39\xe5"
+        );
+    }
+
+    #[test]
+    fn native_xof() {
+        let elements = ["hello", "world"];
+
+        let lthash: LtHash16K12 = elements.iter().collect();
+        let outputs: Vec<_> = elements.iter().map(|e| xof(e, 2048)).collect();
+        assert_eq!(lthash.to_hex_string(), expected_hex(&outputs, 2));
+
+        let lthash: LtHash32K12 = elements.iter().collect();
+        let outputs: Vec<_> = elements.iter().map(|e| xof(e, 4096)).collect();
+        assert_eq!(lthash.to_hex_string(), expected_hex(&outputs, 4));
+    }
+
+    #[test]
+    fn lorum_ipsum() {
+        let lthash: LtHash16K12 = LORUM.lines().collect();
+        assert_eq!(lthash.to_hex_string(), HASH16.trim());
+
+        let lthash: LtHash32K12 = LORUM.lines().collect();
+        assert_eq!(lthash.to_hex_string(), HASH32.trim());
+    }
+}
@@ -0,0 +1,136 @@
+use lthash_rs::journal::{Divergence, Journal, Log, Operand, Operation};
+use sha3::{Sha3_256, Shake128};
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+
+fn journal() -> (Journal<LtHash16, Sha3_256>, LtHash16) {
+    let other = LtHash16::from_iter(["kiwi", "mango"]);
+
+    let mut journal = Journal::new(LtHash16::from_iter(["apple"]));
+    journal.insert("banana");
+    journal.union(&other);
+    journal.remove("apple");
+    journal.difference(&LtHash16::from_iter(["mango"]));
+
+    (journal, other)
+}
+
+#[test]
+fn record() {
+    let (journal, _) = journal();
+
+    let operations: Vec<_> = journal
+        .log()
+        .entries()
+        .iter()
+        .map(|entry| entry.operation)
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            Operation::Insert,
+            Operation::Union,
+            Operation::Remove,
+            Operation::Difference
+        ]
+    );
+    assert_eq!(journal.lthash(), &LtHash16::from_iter(["banana", "kiwi"]));
+}
+
+#[test]
+fn serialize() {
+    let (journal, _) = journal();
+    let bytes = journal.log().to_bytes();
+    assert_eq!(bytes.len(), 6 + 32 + 8 + 4 * (1 + 2 * 32));
+
+    let log = Log::<Sha3_256>::from_bytes(&bytes).unwrap();
+    assert_eq!(&log, journal.log());
+
+    assert!(Log::<Sha3_256>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Log::<sha3::Sha3_512>::from_bytes(&bytes).is_err());
+    let mut unknown = bytes.clone();
+    unknown[6 + 32 + 8] = 4;
+    assert!(Log::<Sha3_256>::from_bytes(&unknown).is_err());
+}
+
+#[test]
+fn replay() {
+    let (journal, other) = journal();
+    let mango = LtHash16::from_iter(["mango"]);
+
+    let replayed = journal.log().replay(
+        LtHash16::from_iter(["apple"]),
+        [
+            Operand::Element(b"banana"),
+            Operand::LtHash(&other),
+            Operand::Element(b"apple"),
+            Operand::LtHash(&mango),
+        ],
+    );
+    assert_eq!(replayed.as_ref(), Ok(journal.lthash()));
+}
+
+#[test]
+fn first_divergent_step() {
+    let (journal, other) = journal();
+    let log = journal.log();
+    let mango = LtHash16::from_iter(["mango"]);
+
+    let replay = |initial: LtHash16, operands: Vec<Operand<LtHash16>>| {
+        log.replay(initial, operands).unwrap_err()
+    };
+
+    assert_eq!(replay(LtHash16::new(), vec![]), Divergence::Initial);
+    assert_eq!(
+        replay(
+            LtHash16::from_iter(["apple"]),
+            vec![Operand::Element(b"banana"), Operand::LtHash(&mango)]
+        ),
+        Divergence::Operand(1)
+    );
+    assert_eq!(
+        replay(
+            LtHash16::from_iter(["apple"]),
+            vec![Operand::Element(b"banana"), Operand::Element(b"kiwi")]
+        ),
+        Divergence::Operand(1)
+    );
+    assert_eq!(
+        replay(
+            LtHash16::from_iter(["apple"]),
+            vec![Operand::Element(b"banana"), Operand::LtHash(&other)]
+        ),
+        Divergence::Operand(2)
+    );
+    assert_eq!(
+        replay(
+            LtHash16::from_iter(["apple"]),
+            vec![
+                Operand::Element(b"banana"),
+                Operand::LtHash(&other),
+                Operand::Element(b"apple"),
+                Operand::LtHash(&mango),
+                Operand::Element(b"apple"),
+            ]
+        ),
+        Divergence::TrailingOperands
+    );
+}
+
+#[test]
+fn tampered_checksum() {
+    let (journal, other) = journal();
+    let mut bytes = journal.log().to_bytes();
+    // the digest of the checksum after the union
+    bytes[6 + 32 + 8 + 65 + 33] ^= 1;
+    let log = Log::<Sha3_256>::from_bytes(&bytes).unwrap();
+
+    let divergence = log
+        .replay(
+            LtHash16::from_iter(["apple"]),
+            [Operand::Element(b"banana"), Operand::LtHash(&other)],
+        )
+        .unwrap_err();
+    assert_eq!(divergence, Divergence::Checksum(1));
+    assert_eq!(divergence.to_string(), "the checksum after step 1 differs");
+}
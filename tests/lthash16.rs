@@ -1,5 +1,7 @@
+use digest::{ExtendableOutput, Update};
 use lthash_rs::LtHash;
-use sha3::Shake128;
+use sha3::{CShake128, CShake128Core, Shake128};
+use subtle::{Choice, ConstantTimeEq};
 
 const LORUM: &str = include_str!("./test-data/lorum-ipsum.txt");
 const HASH: &str = include_str!("./test-data/lorum-hash-16.txt");
@@ -133,3 +135,82 @@ fn invalid_bytes_try_from() {
 
     assert!(lthash.is_err())
 }
+
+#[test]
+fn with_hasher() {
+    let mut lthash = LtHash16::with_hasher(Shake128::default());
+    lthash.extend(["hello", "world"]);
+
+    assert_eq!(lthash, LtHash16::from_iter(["hello", "world"]));
+}
+
+#[test]
+fn customized_hasher() {
+    let hasher = CShake128::from_core(CShake128Core::new(b"customization"));
+    let mut lthash = lthash_rs::LtHash16::with_hasher(hasher.clone());
+    lthash.insert("hello");
+
+    let mut expected = [0u8; 2048];
+    hasher.chain(b"hello").finalize_xof_into(&mut expected);
+    assert_ne!(
+        lthash.clone().into_bytes(),
+        LtHash16::from_iter(["hello"]).into_bytes()
+    );
+    assert_eq!(lthash.into_bytes(), expected);
+}
+
+#[test]
+fn conditional_select() {
+    let apple = LtHash16::from_iter(["apple"]);
+    let banana = LtHash16::from_iter(["banana"]);
+
+    assert!(bool::from(apple.ct_eq(&apple.clone())));
+    assert!(!bool::from(apple.ct_eq(&banana)));
+    assert_eq!(
+        LtHash16::conditional_select(&apple, &banana, Choice::from(0)),
+        apple
+    );
+    assert_eq!(
+        LtHash16::conditional_select(&apple, &banana, Choice::from(1)),
+        banana
+    );
+
+    let mut lthash = apple.clone();
+    lthash.conditional_assign(&banana, Choice::from(0));
+    assert_eq!(lthash, apple);
+    let mut other = banana.clone();
+    LtHash16::conditional_swap(&mut lthash, &mut other, Choice::from(1));
+    assert_eq!((lthash, other), (banana, apple));
+}
+
+#[test]
+fn conditional_insert_remove() {
+    let mut lthash = LtHash16::from_iter(["apple"]);
+    lthash.conditional_insert("banana", Choice::from(1));
+    lthash.conditional_insert("kiwi", Choice::from(0));
+    lthash.conditional_remove("apple", Choice::from(0));
+    assert_eq!(lthash, LtHash16::from_iter(["apple", "banana"]));
+
+    lthash.conditional_remove("apple", Choice::from(1));
+    assert_eq!(lthash, LtHash16::from_iter(["banana"]));
+}
+
+#[test]
+fn insert_weighted() {
+    let mut repeated = LtHash16::from_iter(["apple"]);
+    for _ in 0..1000 {
+        repeated.insert("banana");
+    }
+    repeated.remove("kiwi");
+    repeated.remove("kiwi");
+
+    let mut weighted = LtHash16::from_iter(["apple"]);
+    weighted.insert_weighted("banana", 1000);
+    weighted.insert_weighted("kiwi", -2);
+    weighted.insert_weighted("mango", 0);
+    assert_eq!(weighted, repeated);
+
+    weighted.insert_weighted("banana", -1000);
+    weighted.insert_weighted("kiwi", 2);
+    assert_eq!(weighted, LtHash16::from_iter(["apple"]));
+}
@@ -0,0 +1,231 @@
+use digest::{ExtendableOutput, Update};
+use lthash_rs::LtHash;
+use sha3::{CShake128, CShake128Core, Shake128};
+use subtle::{Choice, ConstantTimeEq};
+
+const LORUM: &str = include_str!("./test-data/lorum-ipsum.txt");
+const HASH: &str = include_str!("./test-data/lorum-hash-20.txt");
+const DATA_MASK: u64 = 0x3fff_fdff_ffef_ffff;
+
+type LtHash20 = lthash_rs::LtHash20<Shake128>;
+
+#[test]
+fn insert_remove_object() {
+    let mut lthash = LtHash20::new();
+    let elements = ["apple", "banana", "kiwi"];
+    lthash.insert(elements[0]);
+    lthash.insert(elements[1]);
+    lthash.insert(elements[2]);
+    lthash.remove(elements[1]);
+    let mut lthash_bis = LtHash20::new();
+    lthash_bis.insert(elements[0]);
+    lthash_bis.insert(elements[2]);
+    assert_eq!(lthash.into_bytes(), lthash_bis.into_bytes());
+}
+
+#[test]
+fn insert_with_extend_remove_object() {
+    let mut lthash = LtHash20::new();
+
+    let objects = vec!["apple", "banana", "kiwi"];
+    lthash.extend(&objects);
+    lthash.remove(b"banana");
+
+    let mut lthash_bis = LtHash20::new();
+    let objects = vec!["apple", "kiwi"];
+    lthash_bis.extend(&objects);
+    assert_eq!(lthash, lthash_bis, "values don't match");
+}
+
+#[test]
+fn lorum_ipsum_insert() {
+    let mut lthash = LtHash20::new();
+    for object in LORUM.lines() {
+        lthash.insert(object);
+    }
+
+    assert_eq!(lthash.to_hex_string(), HASH.trim());
+}
+
+#[test]
+fn lorum_ipsum_insert_with_extend() {
+    let mut lthash = LtHash20::new();
+
+    lthash.extend(LORUM.lines());
+
+    assert_eq!(lthash.to_hex_string(), HASH.trim());
+}
+
+#[test]
+fn union() {
+    let mut left = LtHash20::new();
+    left.insert("hello");
+
+    let mut right = LtHash20::new();
+    right.insert("world");
+
+    assert_eq!(left.union(&right), LtHash20::from_iter(["hello", "world"]));
+}
+
+#[test]
+fn bitor() {
+    let mut left = LtHash20::new();
+    left.insert("hello");
+
+    let mut right = LtHash20::new();
+    right.insert("world");
+
+    assert_eq!(&left | &right, LtHash20::from_iter(["hello", "world"]));
+    assert_eq!(left | right, LtHash20::from_iter(["hello", "world"]));
+}
+
+#[test]
+fn difference() {
+    let mut left = LtHash20::new();
+    left.extend(["hello", "world"]);
+
+    let mut right = LtHash20::new();
+    right.insert("world");
+
+    assert_eq!(left.difference(&right), LtHash20::from_iter(["hello"]));
+}
+
+#[test]
+fn sub() {
+    let mut left = LtHash20::new();
+    left.extend(["hello", "world"]);
+
+    let mut right = LtHash20::new();
+    right.insert("world");
+
+    assert_eq!(&left - &right, LtHash20::from_iter(["hello"]));
+    assert_eq!(left - right, LtHash20::from_iter(["hello"]));
+}
+
+#[test]
+fn into_from_bytes() {
+    let mut left = LtHash20::new();
+    left.extend(["hello", "world"]);
+
+    let bytes = left.into_bytes();
+
+    let right = LtHash20::try_from(bytes.as_ref()).unwrap();
+
+    let mut left = LtHash20::new();
+    left.extend(["hello", "world"]);
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn reset() {
+    let mut reset_lthash = LtHash20::new();
+    reset_lthash.extend(["hello", "world"]);
+    reset_lthash.reset();
+
+    let new_lthash = LtHash20::new();
+
+    assert_eq!(reset_lthash, new_lthash);
+}
+
+#[test]
+fn invalid_bytes_try_from() {
+    let bytes = vec![0u8, 1u8];
+
+    let lthash = LtHash20::try_from(bytes.as_ref());
+
+    assert!(lthash.is_err())
+}
+
+#[test]
+fn remove_wraps_around() {
+    let mut lthash = LtHash20::new();
+    lthash.remove("hello");
+    lthash.insert("hello");
+
+    assert_eq!(lthash, LtHash20::new());
+}
+
+#[test]
+fn with_hasher() {
+    let mut lthash = LtHash20::with_hasher(Shake128::default());
+    lthash.extend(["hello", "world"]);
+
+    assert_eq!(lthash, LtHash20::from_iter(["hello", "world"]));
+}
+
+#[test]
+fn customized_hasher() {
+    let hasher = CShake128::from_core(CShake128Core::new(b"customization"));
+    let mut lthash = lthash_rs::LtHash20::with_hasher(hasher.clone());
+    lthash.insert("hello");
+
+    let mut expected = [0u8; 2688];
+    hasher.chain(b"hello").finalize_xof_into(&mut expected);
+    // the padding bits are cleared
+    for word in expected.chunks_exact_mut(8) {
+        let masked = u64::from_le_bytes(word.try_into().unwrap()) & DATA_MASK;
+        word.copy_from_slice(&masked.to_le_bytes());
+    }
+    assert_ne!(
+        lthash.clone().into_bytes(),
+        LtHash20::from_iter(["hello"]).into_bytes()
+    );
+    assert_eq!(lthash.into_bytes(), expected);
+}
+
+#[test]
+fn conditional_select() {
+    let apple = LtHash20::from_iter(["apple"]);
+    let banana = LtHash20::from_iter(["banana"]);
+
+    assert!(bool::from(apple.ct_eq(&apple.clone())));
+    assert!(!bool::from(apple.ct_eq(&banana)));
+    assert_eq!(
+        LtHash20::conditional_select(&apple, &banana, Choice::from(0)),
+        apple
+    );
+    assert_eq!(
+        LtHash20::conditional_select(&apple, &banana, Choice::from(1)),
+        banana
+    );
+
+    let mut lthash = apple.clone();
+    lthash.conditional_assign(&banana, Choice::from(0));
+    assert_eq!(lthash, apple);
+    let mut other = banana.clone();
+    LtHash20::conditional_swap(&mut lthash, &mut other, Choice::from(1));
+    assert_eq!((lthash, other), (banana, apple));
+}
+
+#[test]
+fn conditional_insert_remove() {
+    let mut lthash = LtHash20::from_iter(["apple"]);
+    lthash.conditional_insert("banana", Choice::from(1));
+    lthash.conditional_insert("kiwi", Choice::from(0));
+    lthash.conditional_remove("apple", Choice::from(0));
+    assert_eq!(lthash, LtHash20::from_iter(["apple", "banana"]));
+
+    lthash.conditional_remove("apple", Choice::from(1));
+    assert_eq!(lthash, LtHash20::from_iter(["banana"]));
+}
+
+#[test]
+fn insert_weighted() {
+    let mut repeated = LtHash20::from_iter(["apple"]);
+    for _ in 0..1000 {
+        repeated.insert("banana");
+    }
+    repeated.remove("kiwi");
+    repeated.remove("kiwi");
+
+    let mut weighted = LtHash20::from_iter(["apple"]);
+    weighted.insert_weighted("banana", 1000);
+    weighted.insert_weighted("kiwi", -2);
+    weighted.insert_weighted("mango", 0);
+    assert_eq!(weighted, repeated);
+
+    weighted.insert_weighted("banana", -1000);
+    weighted.insert_weighted("kiwi", 2);
+    assert_eq!(weighted, LtHash20::from_iter(["apple"]));
+}
@@ -0,0 +1,58 @@
+use lthash_rs::{Lanes, LtHash, LtHashRef};
+use sha3::Shake128;
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type LtHash20 = lthash_rs::LtHash20<Shake128>;
+type LtHash32 = lthash_rs::LtHash32<Shake128>;
+
+fn check<L>()
+where
+    L: Lanes + LtHash + Default + PartialEq + std::fmt::Debug,
+    L: FromIterator<&'static str>,
+{
+    let left = L::from_iter(["apple", "banana"]);
+    let right = L::from_iter(["banana", "kiwi"]);
+    let bytes = left.clone().into_bytes();
+    let view = LtHashRef::<L>::new(&bytes).unwrap();
+
+    assert_eq!(view.as_bytes(), &bytes[..]);
+    assert_eq!(view.to_lthash(), left);
+    assert_eq!(view.union(&right), left.union(&right));
+    assert_eq!(view.difference(&right), left.difference(&right));
+}
+
+#[test]
+fn lthash16() {
+    check::<LtHash16>();
+}
+
+#[test]
+fn lthash20() {
+    check::<LtHash20>();
+}
+
+#[test]
+fn lthash32() {
+    check::<LtHash32>();
+}
+
+#[test]
+fn wrong_number_of_bytes() {
+    assert!(LtHashRef::<LtHash16>::new(&[0; 2047]).is_err());
+    assert!(LtHashRef::<LtHash20>::new(&[0; 2048]).is_err());
+    assert!(LtHashRef::<LtHash32>::try_from(&[0; 4097][..]).is_err());
+}
+
+#[test]
+fn padding_bits() {
+    let mut bytes = [0; 2688];
+    assert!(LtHashRef::<LtHash20>::new(&bytes).is_ok());
+
+    bytes[7] = 0x80;
+    assert!(LtHashRef::<LtHash20>::new(&bytes).is_err());
+}
+
+#[test]
+fn checksum_is_boxed() {
+    assert!(std::mem::size_of::<LtHash32>() < 1024);
+}
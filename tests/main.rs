@@ -1,2 +1,25 @@
+mod algorithm;
+mod blake2xb;
+mod checked;
+mod cli;
+mod compression;
+mod concurrent;
+mod counted;
+mod dynamic;
+mod expander;
+mod folly;
+mod generic;
+mod hashers;
+mod journal;
 mod lthash16;
+mod lthash20;
 mod lthash32;
+mod lthash_ref;
+mod multi;
+mod pair;
+mod sequence;
+mod store;
+mod stream;
+mod timing;
+mod transaction;
+mod wire;
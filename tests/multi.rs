@@ -0,0 +1,59 @@
+use lthash_rs::{LtHash, MultiLtHash};
+use sha3::{Shake128, Shake256};
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type LtHash20 = lthash_rs::LtHash20<Shake256>;
+type LtHash32 = lthash_rs::LtHash32<Shake256>;
+type Multi = MultiLtHash<(LtHash16, LtHash32)>;
+
+#[test]
+fn forward_to_members() {
+    let mut multi = Multi::from_iter(["apple", "banana", "kiwi"]);
+    multi.remove("banana");
+
+    assert_eq!(
+        multi.clone().into_inner(),
+        (
+            LtHash16::from_iter(["apple", "kiwi"]),
+            LtHash32::from_iter(["apple", "kiwi"])
+        )
+    );
+    assert_eq!(
+        multi.to_hex_string(),
+        format!(
+            "{}:{}",
+            multi.0 .0.to_hex_string(),
+            multi.0 .1.to_hex_string()
+        )
+    );
+
+    multi.reset();
+    assert_eq!(multi, Multi::default());
+}
+
+#[test]
+fn union_difference() {
+    let left = Multi::from_iter(["apple", "banana"]);
+    let right = Multi::from_iter(["banana"]);
+
+    assert_eq!(&left - &right, Multi::from_iter(["apple"]));
+    assert_eq!(&(&left | &right) - &left, right);
+}
+
+#[test]
+fn serialize() {
+    let multi = MultiLtHash::<(LtHash16, LtHash20, LtHash32)>::from_iter([
+        "apple", "banana",
+    ]);
+    let bytes = multi.clone().into_bytes();
+    assert_eq!(bytes.len(), 3 * 4 + 2048 + 2688 + 4096);
+    assert_eq!(&bytes[..4], &2048u32.to_le_bytes());
+
+    assert_eq!(MultiLtHash::try_from(&bytes[..]), Ok(multi));
+    assert!(Multi::try_from(&bytes[..]).is_err());
+    assert!(Multi::try_from(&bytes[..4 + 2048 + 4 + 100]).is_err());
+
+    let mut trailing = Multi::default().into_bytes();
+    trailing.push(0);
+    assert!(Multi::try_from(&trailing[..]).is_err());
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "sha3")]
+
+use lthash_rs::{LtHash, LtHashPair};
+use sha3::{CShake128, CShake128Core, Shake128};
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type LtHash32 = lthash_rs::LtHash32<Shake128>;
+type Pair = LtHashPair<Shake128>;
+
+#[test]
+fn same_checksums() {
+    let mut pair = Pair::from_iter(["apple", "banana", "kiwi"]);
+    pair.remove("banana");
+
+    let (lthash16, lthash32) = pair.into_parts();
+    assert_eq!(lthash16, LtHash16::from_iter(["apple", "kiwi"]));
+    assert_eq!(lthash32, LtHash32::from_iter(["apple", "kiwi"]));
+}
+
+#[test]
+fn union_difference() {
+    let left = Pair::from_iter(["apple", "banana"]);
+    let right = Pair::from_iter(["banana"]);
+
+    assert_eq!(&left - &right, Pair::from_iter(["apple"]));
+    assert_eq!(
+        &left | &right,
+        Pair::from_parts(
+            LtHash16::from_iter(["apple", "banana", "banana"]),
+            LtHash32::from_iter(["apple", "banana", "banana"])
+        )
+    );
+
+    let mut pair = left.clone();
+    pair.reset();
+    assert_eq!(pair, Pair::new());
+}
+
+#[test]
+fn serialize() {
+    let pair = Pair::from_iter(["apple", "banana"]);
+    let bytes = pair.clone().into_bytes();
+    assert_eq!(bytes.len(), 2048 + 4096);
+    assert_eq!(&bytes[..2048], pair.lthash16().clone().into_bytes());
+    assert_eq!(
+        pair.to_hex_string(),
+        pair.lthash16().to_hex_string() + &pair.lthash32().to_hex_string()
+    );
+
+    assert_eq!(Pair::try_from(&bytes[..]), Ok(pair));
+    assert!(Pair::try_from(&bytes[..2048]).is_err());
+}
+
+#[test]
+fn custom_hasher() {
+    let hasher = CShake128::from_core(CShake128Core::new(b"customization"));
+    let mut pair = LtHashPair::with_hasher(hasher.clone());
+    pair.insert("apple");
+
+    let mut lthash16 = lthash_rs::LtHash16::with_hasher(hasher.clone());
+    lthash16.insert("apple");
+    let mut lthash32 = lthash_rs::LtHash32::with_hasher(hasher);
+    lthash32.insert("apple");
+    assert_eq!(pair, LtHashPair::from_parts(lthash16, lthash32));
+}
@@ -0,0 +1,94 @@
+use sha3::Shake128;
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type Sequence = lthash_rs::SequenceLtHash<LtHash16>;
+
+fn sequence(elements: &[&str]) -> Sequence {
+    let mut sequence = Sequence::default();
+    sequence.extend(elements);
+    sequence
+}
+
+#[test]
+fn order_matters() {
+    assert_ne!(sequence(&["a", "b"]), sequence(&["b", "a"]));
+    assert_ne!(
+        sequence(&["a", "b"]).lthash(),
+        &LtHash16::from_iter(["a", "b"])
+    );
+    assert_eq!(sequence(&["a", "b"]).len(), 2);
+
+    // the position has a fixed width, so it cannot absorb the element
+    assert_ne!(sequence(&["", "a"]), sequence(&["\x01", ""]));
+}
+
+#[test]
+fn push_pop() {
+    let mut lthash = sequence(&["a", "b", "c"]);
+    lthash.pop("c").unwrap();
+    assert_eq!(lthash, sequence(&["a", "b"]));
+    lthash.pop("b").unwrap();
+    lthash.pop("a").unwrap();
+    assert!(lthash.is_empty());
+    assert_eq!(lthash, Sequence::default());
+    assert!(lthash.pop("a").is_err());
+}
+
+#[test]
+fn replace() {
+    let mut lthash = sequence(&["a", "b", "c"]);
+    lthash.replace(1, "b", "x").unwrap();
+    assert_eq!(lthash, sequence(&["a", "x", "c"]));
+    assert!(lthash.replace(3, "d", "x").is_err());
+}
+
+#[test]
+fn splice() {
+    let mut lthash = sequence(&["a", "b", "c", "d"]);
+    lthash.splice(1, ["b", "c"], ["x", "y"]).unwrap();
+    assert_eq!(lthash, sequence(&["a", "x", "y", "d"]));
+
+    lthash.splice(2, ["y", "d"], ["z"]).unwrap();
+    assert_eq!(lthash, sequence(&["a", "x", "z"]));
+    lthash.splice(3, [""; 0], ["e", "f"]).unwrap();
+    assert_eq!(lthash, sequence(&["a", "x", "z", "e", "f"]));
+}
+
+#[test]
+fn invalid_splice() {
+    let mut lthash = sequence(&["a", "b", "c"]);
+    // "c" would move
+    assert!(lthash.splice(0, ["a"], ["x", "y"]).is_err());
+    assert!(lthash.splice(2, ["c", "d"], ["x", "y"]).is_err());
+    assert!(lthash.splice(4, [""; 0], ["x"]).is_err());
+    assert_eq!(lthash, sequence(&["a", "b", "c"]));
+}
+
+#[test]
+fn concat() {
+    let ab = sequence(&["a", "b"]);
+    let mut cd = Sequence::with_offset(2);
+    cd.extend(["c", "d"]);
+    let abcd = ab.concat(&cd).unwrap();
+    assert_eq!(abcd, sequence(&["a", "b", "c", "d"]));
+    assert_eq!((abcd.offset(), abcd.len()), (0, 4));
+
+    assert!(cd.concat(&ab).is_err());
+    assert!(ab.concat(&sequence(&["c"])).is_err());
+}
+
+#[test]
+fn position_overflow() {
+    let mut lthash = Sequence::with_offset(u64::MAX);
+    lthash.push("a").unwrap();
+    assert_eq!(
+        lthash.push("b").unwrap_err(),
+        "The position 18446744073709551615 + 1 overflows a u64."
+    );
+    assert_eq!(lthash.len(), 1);
+
+    let end = Sequence::from_parts(LtHash16::new(), u64::MAX, 0);
+    assert!(end.concat(&Sequence::default()).is_err());
+    let last = Sequence::from_parts(LtHash16::new(), 1, u64::MAX);
+    assert!(last.concat(&Sequence::with_offset(0)).is_err());
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "store")]
+
+use std::path::PathBuf;
+
+use lthash_rs::{store::Store, Blake2xb, LtHash};
+
+type LtHash32 = lthash_rs::LtHash32<Blake2xb>;
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("lthash-store-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn insert_remove_sync() {
+    let path = temp_path("insert-remove");
+    let mut store = Store::<Blake2xb>::open(&path).unwrap();
+    assert!(store.is_empty());
+
+    store.insert("left", "apple");
+    store.insert("left", "banana");
+    store.insert("right", "kiwi");
+    store.remove("left", "banana");
+    assert_eq!(store.len(), 2);
+    assert_eq!(
+        store.get("left").unwrap().to_lthash(),
+        LtHash32::from_iter(["apple"])
+    );
+    store.sync().unwrap();
+
+    // updates in place after the sync
+    store.insert("right", "banana");
+    store.sync().unwrap();
+    drop(store);
+
+    let store = Store::<Blake2xb>::open(&path).unwrap();
+    assert_eq!(store.keys().collect::<Vec<_>>(), [&b"left"[..], b"right"]);
+    assert_eq!(
+        store.get("left").unwrap().to_lthash(),
+        LtHash32::from_iter(["apple"])
+    );
+    assert_eq!(
+        store.get("right").unwrap().to_lthash(),
+        LtHash32::from_iter(["kiwi", "banana"])
+    );
+    assert!(store.get("missing").is_none());
+}
+
+#[test]
+fn unsynced_changes_are_not_written() {
+    let path = temp_path("unsynced");
+    let mut store = Store::<Blake2xb>::open(&path).unwrap();
+    store.insert("key", "apple");
+    store.sync().unwrap();
+
+    store.insert("key", "banana");
+    store.insert("other", "kiwi");
+    drop(store);
+
+    let store = Store::<Blake2xb>::open(&path).unwrap();
+    assert_eq!(store.len(), 1);
+    assert_eq!(
+        store.get("key").unwrap().to_lthash(),
+        LtHash32::from_iter(["apple"])
+    );
+}
+
+#[test]
+fn file_format() {
+    let path = temp_path("format");
+    let mut store = Store::<Blake2xb>::open(&path).unwrap();
+    store.insert("key", "apple");
+    store.sync().unwrap();
+
+    let bytes = std::fs::read(&path).unwrap();
+    assert_eq!(&bytes[..8], b"LTHASH32");
+    assert_eq!(
+        bytes[8..32],
+        [
+            1, 0, 0, 0, 0, 16, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0,
+            0, 0
+        ]
+    );
+    assert_eq!(bytes[32..4128], LtHash32::from_iter(["apple"]).into_bytes());
+    assert_eq!(bytes[4128..], [3, 0, 0, 0, b'k', b'e', b'y']);
+}
+
+#[test]
+fn invalid_files() {
+    let path = temp_path("invalid");
+
+    std::fs::write(&path, b"not a checksum store").unwrap();
+    assert!(Store::<Blake2xb>::open(&path).is_err());
+
+    // a record is missing
+    let mut bytes = b"LTHASH32".to_vec();
+    bytes.extend([1, 0, 0, 0, 0, 16, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+    bytes.extend([3, 0, 0, 0, 0, 0, 0, 0]);
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(Store::<Blake2xb>::open(&path).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn sync_in_place() {
+    use std::os::unix::fs::MetadataExt;
+
+    let path = temp_path("in-place");
+    let mut store = Store::<Blake2xb>::open(&path).unwrap();
+    store.insert("key", "apple");
+    store.sync().unwrap();
+    let inode = std::fs::metadata(&path).unwrap().ino();
+
+    store.insert("key", "banana");
+    store.insert("other", "kiwi");
+    store.sync().unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().ino(), inode);
+    assert!(!wal_path(&path).exists());
+}
+
+#[test]
+fn mismatched_hash_function() {
+    let path = temp_path("mismatched");
+    drop(Store::<Blake2xb>::open(&path).unwrap());
+
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[24] = 1;
+    std::fs::write(&path, &bytes).unwrap();
+    let err = Store::<Blake2xb>::open(&path).err().unwrap();
+    assert_eq!(err.to_string(), "Expected hash function 3, found 1.");
+}
+
+fn wal_path(path: &std::path::Path) -> PathBuf {
+    let mut name = path.file_name().unwrap().to_os_string();
+    name.push(".wal");
+    path.with_file_name(name)
+}
+
+/// A write-ahead log replacing the first record with `record`.
+fn wal(len: usize, record: &[u8]) -> Vec<u8> {
+    let mut wal = b"LTHASHWL".to_vec();
+    wal.extend((len as u64).to_le_bytes());
+    wal.extend(1u32.to_le_bytes());
+    wal.extend(32u64.to_le_bytes());
+    wal.extend((record.len() as u32).to_le_bytes());
+    wal.extend(record);
+    let crc = crc32fast::hash(&wal);
+    wal.extend(crc.to_le_bytes());
+    wal
+}
+
+#[test]
+fn recovery() {
+    let path = temp_path("recovery");
+    let mut store = Store::<Blake2xb>::open(&path).unwrap();
+    store.insert("key", "apple");
+    store.sync().unwrap();
+    drop(store);
+    let len = std::fs::metadata(&path).unwrap().len() as usize;
+    let both = LtHash32::from_iter(["apple", "banana"]).into_bytes();
+
+    // torn log, left by a crash while it was written
+    let mut torn = wal(len, &both);
+    torn.pop();
+    std::fs::write(wal_path(&path), torn).unwrap();
+    let store = Store::<Blake2xb>::open(&path).unwrap();
+    assert_eq!(
+        store.get("key").unwrap().to_lthash(),
+        LtHash32::from_iter(["apple"])
+    );
+    assert!(!wal_path(&path).exists());
+    drop(store);
+
+    // complete log, left by a crash while it was applied
+    std::fs::write(wal_path(&path), wal(len, &both)).unwrap();
+    let store = Store::<Blake2xb>::open(&path).unwrap();
+    assert_eq!(
+        store.get("key").unwrap().to_lthash(),
+        LtHash32::from_iter(["apple", "banana"])
+    );
+    assert!(!wal_path(&path).exists());
+}
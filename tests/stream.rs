@@ -0,0 +1,68 @@
+#![cfg(feature = "futures")]
+
+use futures::{executor::block_on, io::AsyncWriteExt, stream, SinkExt};
+use lthash_rs::{IncrementalLtHash, LtHashStreamExt};
+use sha3::Shake128;
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+type LtHash20 = lthash_rs::LtHash20<Shake128>;
+type LtHash32 = lthash_rs::LtHash32<Shake128>;
+
+#[test]
+fn collect() {
+    let elements = ["apple", "banana", "kiwi"];
+
+    let lthash: LtHash16 = block_on(stream::iter(elements).collect_lthash());
+    assert_eq!(lthash, LtHash16::from_iter(elements));
+    let lthash: LtHash20 = block_on(stream::iter(elements).collect_lthash());
+    assert_eq!(lthash, LtHash20::from_iter(elements));
+    let lthash: LtHash32 = block_on(stream::iter(elements).collect_lthash());
+    assert_eq!(lthash, LtHash32::from_iter(elements));
+
+    let lthash = block_on(
+        stream::iter(["banana"]).extend_lthash(LtHash16::from_iter(["apple"])),
+    );
+    assert_eq!(lthash, LtHash16::from_iter(["apple", "banana"]));
+}
+
+#[test]
+fn sink() {
+    let mut lthash = LtHash32::new();
+    block_on(async {
+        lthash.send("apple").await.unwrap();
+        lthash
+            .send_all(&mut stream::iter(["banana", "kiwi"].map(Ok)))
+            .await
+            .unwrap();
+    });
+    assert_eq!(lthash, LtHash32::from_iter(["apple", "banana", "kiwi"]));
+}
+
+#[test]
+fn element_writer() {
+    let mut lthash = LtHash20::from_iter(["apple", "banana"]);
+    block_on(async {
+        let mut writer = lthash.insert_writer();
+        writer.write_all(b"ki").await?;
+        writer.write_all(b"wi").await?;
+        writer.close().await?;
+        assert!(writer.write_all(b"!").await.is_err());
+
+        let mut writer = lthash.remove_writer();
+        futures::io::copy(&b"apple"[..], &mut writer).await?;
+        writer.close().await
+    })
+    .unwrap();
+    assert_eq!(lthash, LtHash20::from_iter(["banana", "kiwi"]));
+}
+
+#[test]
+fn unclosed_writer() {
+    let mut lthash = LtHash16::new();
+    block_on(async {
+        let mut writer = lthash.insert_writer();
+        writer.write_all(b"apple").await
+    })
+    .unwrap();
+    assert_eq!(lthash, LtHash16::new());
+}
@@ -0,0 +1,84 @@
+/*
+   Writes BLAKE2Xb vectors in the format of the known-answer tests of the
+   BLAKE2 reference repository (testvectors/blake2xb-kat.txt), unkeyed then
+   keyed, with the input and key used by the reference self-test.
+
+   The expansion follows blake2xb-ref.c, on top of the reference BLAKE2b of
+   blake2b-ref.c; see README.md for how to build it.
+*/
+
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "blake2.h"
+
+static int blake2xb(uint8_t *out, size_t outlen, const uint8_t *in,
+                    size_t inlen, const uint8_t *key, size_t keylen) {
+  blake2b_state S;
+  blake2b_param P;
+  uint8_t root[BLAKE2B_OUTBYTES];
+  size_t i;
+
+  memset(&P, 0, sizeof(P));
+  P.digest_length = BLAKE2B_OUTBYTES;
+  P.key_length = (uint8_t)keylen;
+  P.fanout = 1;
+  P.depth = 1;
+  P.xof_length = (uint32_t)outlen;
+  if (blake2b_init_param(&S, &P) < 0) return -1;
+  if (keylen > 0) {
+    uint8_t block[BLAKE2B_BLOCKBYTES];
+    memset(block, 0, BLAKE2B_BLOCKBYTES);
+    memcpy(block, key, keylen);
+    blake2b_update(&S, block, BLAKE2B_BLOCKBYTES);
+  }
+  blake2b_update(&S, in, inlen);
+  blake2b_final(&S, root, BLAKE2B_OUTBYTES);
+
+  P.key_length = 0;
+  P.fanout = 0;
+  P.depth = 0;
+  P.leaf_length = BLAKE2B_OUTBYTES;
+  P.inner_length = BLAKE2B_OUTBYTES;
+  P.node_depth = 0;
+  for (i = 0; outlen > 0; ++i) {
+    size_t block = outlen < BLAKE2B_OUTBYTES ? outlen : BLAKE2B_OUTBYTES;
+    P.digest_length = (uint8_t)block;
+    P.node_offset = (uint32_t)i;
+    blake2b_init_param(&S, &P);
+    blake2b_update(&S, root, BLAKE2B_OUTBYTES);
+    blake2b_final(&S, out + i * BLAKE2B_OUTBYTES, block);
+    outlen -= block;
+  }
+  return 0;
+}
+
+static void print_hex(const char *label, const uint8_t *bytes, size_t len) {
+  size_t i;
+  printf("%s\t", label);
+  for (i = 0; i < len; ++i) printf("%02x", bytes[i]);
+  printf("\n");
+}
+
+int main(void) {
+  static const size_t outlens[] = {1,   2,   3,   32,  63,  64,  65,
+                                   127, 128, 129, 200, 255, 256};
+  uint8_t in[256], key[BLAKE2B_KEYBYTES], out[256];
+  size_t i, keyed;
+
+  for (i = 0; i < sizeof(in); ++i) in[i] = (uint8_t)i;
+  for (i = 0; i < sizeof(key); ++i) key[i] = (uint8_t)i;
+
+  for (keyed = 0; keyed < 2; ++keyed) {
+    for (i = 0; i < sizeof(outlens) / sizeof(outlens[0]); ++i) {
+      size_t keylen = keyed ? sizeof(key) : 0;
+      if (blake2xb(out, outlens[i], in, sizeof(in), key, keylen) < 0) return 1;
+      print_hex("in:", in, sizeof(in));
+      print_hex("key:", key, keylen);
+      print_hex("hash:", out, outlens[i]);
+      printf("\n");
+    }
+  }
+  return 0;
+}
@@ -0,0 +1,212 @@
+#!/usr/bin/env python3
+"""Generates the folly compatibility test vectors.
+
+BLAKE2xb is computed with a pure Python BLAKE2b taking a raw parameter block
+(hashlib rejects the `depth = 0` required by the BLAKE2X expansion nodes),
+which is checked against hashlib's reference BLAKE2b first. The checksums
+follow folly's `LtHash<B, N>`: every object is expanded with BLAKE2xb to the
+size of the checksum (`getChecksumSizeBytes()`), padding bits are cleared and
+elements are summed modulo 2^B, in little-endian order.
+
+Usage: python3 generate.py (from this directory)
+"""
+
+import hashlib
+import struct
+
+MASK64 = (1 << 64) - 1
+IV = [
+    0x6A09E667F3BCC908, 0xBB67AE8584CAA73B, 0x3C6EF372FE94F82B,
+    0xA54FF53A5F1D36F1, 0x510E527FADE682D1, 0x9B05688C2B3E6C1F,
+    0x1F83D9ABFB41BD6B, 0x5BE0CD19137E2179,
+]
+SIGMA = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+]
+
+
+def rotr(x, n):
+    return ((x >> n) | (x << (64 - n))) & MASK64
+
+
+def compress(h, block, t, last):
+    m = struct.unpack("<16Q", block)
+    v = h + IV
+    v[12] ^= t & MASK64
+    v[13] ^= t >> 64
+    if last:
+        v[14] ^= MASK64
+    for r in range(12):
+        s = SIGMA[r % 10]
+        for i, (a, b, c, d) in enumerate(
+            [(0, 4, 8, 12), (1, 5, 9, 13), (2, 6, 10, 14), (3, 7, 11, 15),
+             (0, 5, 10, 15), (1, 6, 11, 12), (2, 7, 8, 13), (3, 4, 9, 14)]
+        ):
+            x, y = m[s[2 * i]], m[s[2 * i + 1]]
+            v[a] = (v[a] + v[b] + x) & MASK64
+            v[d] = rotr(v[d] ^ v[a], 32)
+            v[c] = (v[c] + v[d]) & MASK64
+            v[b] = rotr(v[b] ^ v[c], 24)
+            v[a] = (v[a] + v[b] + y) & MASK64
+            v[d] = rotr(v[d] ^ v[a], 16)
+            v[c] = (v[c] + v[d]) & MASK64
+            v[b] = rotr(v[b] ^ v[c], 63)
+    return [h[i] ^ v[i] ^ v[i + 8] for i in range(8)]
+
+
+def blake2b(data, digest_length=64, key=b"", fanout=1, depth=1, leaf_length=0,
+            node_offset=0, xof_length=0, node_depth=0, inner_length=0,
+            salt=b"", person=b""):
+    param = struct.pack(
+        "<BBBBIIIBB14x16s16s", digest_length, len(key), fanout, depth,
+        leaf_length, node_offset, xof_length, node_depth, inner_length,
+        salt.ljust(16, b"\0"), person.ljust(16, b"\0"),
+    )
+    h = [iv ^ p for iv, p in zip(IV, struct.unpack("<8Q", param))]
+    if key:
+        data = key.ljust(128, b"\0") + data
+    blocks = [data[i:i + 128] for i in range(0, len(data), 128)] or [b""]
+    for i, block in enumerate(blocks):
+        last = i == len(blocks) - 1
+        t = len(data) if last else (i + 1) * 128
+        h = compress(h, block.ljust(128, b"\0"), t, last)
+    return struct.pack("<8Q", *h)[:digest_length]
+
+
+UNKNOWN_LENGTH = 0xFFFFFFFF
+
+
+def blake2xb(data, length, key=b"", salt=b"", person=b"", xof_length=None):
+    """The first `length` bytes of BLAKE2xb, announcing `xof_length` bytes."""
+    xof_length = length if xof_length is None else xof_length
+    root = blake2b(data, key=key, xof_length=xof_length, salt=salt,
+                   person=person)
+    out = b""
+    for i in range((length + 63) // 64):
+        if xof_length == UNKNOWN_LENGTH:
+            digest_length = 64
+        else:
+            digest_length = min(64, xof_length - 64 * i)
+        out += blake2b(
+            root, digest_length=digest_length, fanout=0, depth=0,
+            leaf_length=64, node_offset=i, xof_length=xof_length,
+            inner_length=64, salt=salt, person=person,
+        )
+    return out[:length]
+
+
+def check_blake2b():
+    for data in [b"", b"abc", bytes(range(256)) * 3]:
+        for kwargs in [
+            {}, {"key": b"key"}, {"salt": b"salt", "person": b"person"},
+            {"digest_length": 32, "fanout": 0, "depth": 2, "leaf_length": 64,
+             "node_offset": 5, "node_depth": 1, "inner_length": 64},
+        ]:
+            py = dict(kwargs)
+            for ours, theirs in [("digest_length", "digest_size"),
+                                 ("leaf_length", "leaf_size"),
+                                 ("inner_length", "inner_size")]:
+                if ours in py:
+                    py[theirs] = py.pop(ours)
+            expected = hashlib.blake2b(data, **py).digest()
+            assert blake2b(data, **kwargs) == expected, kwargs
+
+
+# (bits per element, element count, data mask of every 64-bit word)
+VARIANTS = [
+    (16, 1024, MASK64),
+    (20, 1008, 0x3FFFFDFFFFEFFFFF),
+    (32, 1024, MASK64),
+]
+ELEMENTS = [b"", b"hello", b"world"]
+# set with `LtHash::setKey`, which requires 16 to 64 bytes
+KEY = b"folly-lthash-key-0123456789abcde"
+
+
+def lanes(words, bits):
+    """Splits the 64-bit words into elements, skipping the padding bits."""
+    stride = bits + 1 if bits == 20 else bits
+    per_word = 64 // stride
+    return [(w >> (stride * i)) & ((1 << bits) - 1)
+            for w in words for i in range(per_word)]
+
+
+def pack(elements, bits):
+    stride = bits + 1 if bits == 20 else bits
+    per_word = 64 // stride
+    words = []
+    for i in range(0, len(elements), per_word):
+        word = 0
+        for j, e in enumerate(elements[i:i + per_word]):
+            word |= e << (stride * j)
+        words.append(word)
+    return struct.pack("<%dQ" % len(words), *words)
+
+
+def checksum(objects, bits, count, mask, key=b""):
+    size = bits * count // 8 if bits != 20 else count // 3 * 8
+    total = [0] * count
+    for obj in objects:
+        out = blake2xb(obj, size, key=key)
+        words = [w & mask for w in struct.unpack("<%dQ" % (size // 8), out)]
+        for i, e in enumerate(lanes(words, bits)):
+            total[i] = (total[i] + e) % (1 << bits)
+    return pack(total, bits)
+
+
+# (key, salt, personalization, announced output length, output length)
+PARAMS = [
+    (b"", b"", b"", 1, 1),
+    (b"", b"", b"", 64, 64),
+    (b"", b"", b"", 65, 65),
+    (bytes(range(64)), b"", b"", 200, 200),
+    (b"key", b"salt", b"personalization", 129, 129),
+    (b"", b"", b"", UNKNOWN_LENGTH, 200),
+    (b"key", b"", b"", UNKNOWN_LENGTH, 100),
+]
+
+
+def main():
+    check_blake2b()
+    # BLAKE2xb with parameters, on an input longer than a block
+    with open("blake2xb-params.txt", "w") as f:
+        data = bytes(range(256))
+        for key, salt, person, xof_length, length in PARAMS:
+            out = blake2xb(data, length, key=key, salt=salt, person=person,
+                           xof_length=xof_length)
+            f.write("%s %s %s %d %d %s\n" % (
+                key.hex() or "-", salt.hex() or "-", person.hex() or "-",
+                xof_length, length, out.hex(),
+            ))
+    for bits, count, mask in VARIANTS:
+        size = bits * count // 8 if bits != 20 else count // 3 * 8
+        with open("blake2xb-%d.txt" % size, "w") as f:
+            for obj in ELEMENTS:
+                f.write("%s %s\n" % (obj.hex() or "-", blake2xb(obj, size).hex()))
+        # the checksum of the first `n` elements, for every `n`
+        with open("lthash%d.txt" % bits, "w") as f:
+            for n in range(len(ELEMENTS) + 1):
+                objects = ELEMENTS[:n]
+                f.write("%d %s\n" % (
+                    n, checksum(objects, bits, count, mask).hex()
+                ))
+        if bits == 16:
+            with open("lthash16-keyed.txt", "w") as f:
+                for n in range(len(ELEMENTS) + 1):
+                    objects = ELEMENTS[:n]
+                    f.write("%d %s\n" % (
+                        n, checksum(objects, bits, count, mask, KEY).hex()
+                    ))
+
+
+if __name__ == "__main__":
+    main()
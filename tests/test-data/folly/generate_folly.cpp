@@ -0,0 +1,53 @@
+// Generates the folly compatibility test vectors with folly's own `LtHash`,
+// in the format of the files written by `generate.py`: on every line, the
+// number `n` of elements and the hex `getChecksum()` of the first `n` ones.
+//
+// See README.md for how to build and run it.
+
+#include <cstdio>
+#include <string>
+#include <vector>
+
+#include <folly/Range.h>
+#include <folly/String.h>
+#include <folly/crypto/LtHash.h>
+#include <sodium.h>
+
+namespace {
+
+const std::vector<std::string> kElements = {"", "hello", "world"};
+// set with `LtHash::setKey`, which requires 16 to 64 bytes
+const std::string kKey = "folly-lthash-key-0123456789abcde";
+
+template <std::size_t B, std::size_t N>
+void write(const char* path, const std::string& key) {
+  FILE* file = std::fopen(path, "w");
+  for (std::size_t n = 0; n <= kElements.size(); ++n) {
+    folly::crypto::LtHash<B, N> lthash;
+    if (!key.empty()) {
+      lthash.setKey(folly::range(key));
+    }
+    for (std::size_t i = 0; i < n; ++i) {
+      lthash.addObject(folly::range(kElements[i]));
+    }
+    std::fprintf(
+        file,
+        "%zu %s\n",
+        n,
+        folly::hexlify(lthash.getChecksum()).c_str());
+  }
+  std::fclose(file);
+}
+
+} // namespace
+
+int main() {
+  if (sodium_init() < 0) {
+    return 1;
+  }
+  write<16, 1024>("lthash16.txt", "");
+  write<16, 1024>("lthash16-keyed.txt", kKey);
+  write<20, 1008>("lthash20.txt", "");
+  write<32, 1024>("lthash32.txt", "");
+  return 0;
+}
@@ -0,0 +1,142 @@
+// dudect-style timing tests ("Dude, is my code constant time?", Reparaz,
+// Balasch and Verbauwhede): the running times of an operation on two classes
+// of inputs, interleaved at random, are compared with Welch's t-test, a large
+// |t| revealing a timing leak.
+//
+// They are slow and sensitive to the noise of the machine, so they are ignored
+// by default:
+//
+//     cargo test --release --test main timing -- --ignored --test-threads 1
+
+use std::{hint::black_box, time::Instant};
+
+use digest::{ExtendableOutput, Update, XofReader};
+use lthash_rs::LtHash;
+use sha3::Shake128;
+use subtle::Choice;
+
+const MEASUREMENTS: usize = 20_000;
+// the threshold of dudect above which the operation is definitely not constant
+// time
+const THRESHOLD: f64 = 10.0;
+
+fn random_bytes(seed: &str, len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    Shake128::default()
+        .chain(seed)
+        .finalize_xof()
+        .read(&mut bytes);
+    bytes
+}
+
+/// Returns |t| of the running times of `op` on the classes 0 and 1.
+fn t_statistic(mut op: impl FnMut(usize, usize)) -> f64 {
+    let classes = random_bytes("classes", MEASUREMENTS);
+    let mut times: Vec<(usize, f64)> = classes
+        .iter()
+        .enumerate()
+        .map(|(i, class)| {
+            let class = usize::from(class & 1);
+            let start = Instant::now();
+            op(i, class);
+            (class, start.elapsed().as_nanos() as f64)
+        })
+        .collect();
+
+    // crops the outliers, e.g. the measurements interrupted by the scheduler
+    let mut sorted: Vec<f64> = times.iter().map(|(_, time)| *time).collect();
+    sorted.sort_by(f64::total_cmp);
+    let cutoff = sorted[sorted.len() * 9 / 10];
+    times.retain(|(_, time)| *time <= cutoff);
+
+    let mut n = [0f64; 2];
+    let mut mean = [0f64; 2];
+    let mut m2 = [0f64; 2];
+    for (class, time) in times {
+        n[class] += 1.0;
+        let delta = time - mean[class];
+        mean[class] += delta / n[class];
+        m2[class] += delta * (time - mean[class]);
+    }
+    let var = [m2[0] / (n[0] - 1.0), m2[1] / (n[1] - 1.0)];
+    ((mean[0] - mean[1]) / (var[0] / n[0] + var[1] / n[1]).sqrt()).abs()
+}
+
+macro_rules! timing {
+    ($name:ident, $lthash:ty) => {
+        mod $name {
+            use super::*;
+
+            type L = $lthash;
+
+            #[test]
+            #[ignore]
+            fn eq() {
+                let lthash = L::from_iter(["apple", "banana"]);
+                // differs in the first lane only, where an early exit would
+                // be the fastest
+                let mut bytes = lthash.clone().into_bytes();
+                bytes[0] ^= 1;
+                let others = [lthash.clone(), L::try_from(&bytes[..]).unwrap()];
+
+                let t = t_statistic(|_, class| {
+                    black_box(black_box(&lthash) == black_box(&others[class]));
+                });
+                assert!(t < THRESHOLD, "t = {t}");
+            }
+
+            #[test]
+            #[ignore]
+            fn insert_remove() {
+                let random = random_bytes("elements", 64 * MEASUREMENTS);
+                let mut lthash = L::new();
+
+                let t = t_statistic(|i, class| {
+                    let element = match class {
+                        0 => &[0u8; 64][..],
+                        _ => &random[64 * i..64 * (i + 1)],
+                    };
+                    if i % 2 == 0 {
+                        lthash.insert(black_box(element));
+                    } else {
+                        lthash.remove(black_box(element));
+                    }
+                });
+                assert!(t < THRESHOLD, "t = {t}");
+            }
+
+            #[test]
+            #[ignore]
+            fn conditional_insert_remove() {
+                let mut lthash = L::new();
+
+                let t = t_statistic(|i, class| {
+                    let choice = Choice::from(black_box(class as u8));
+                    if i % 2 == 0 {
+                        lthash.conditional_insert("apple", choice);
+                    } else {
+                        lthash.conditional_remove("apple", choice);
+                    }
+                });
+                assert!(t < THRESHOLD, "t = {t}");
+            }
+
+            #[test]
+            #[ignore]
+            fn conditional_assign() {
+                let mut lthash = L::from_iter(["apple"]);
+                let other = L::from_iter(["banana"]);
+
+                let t = t_statistic(|_, class| {
+                    let choice = Choice::from(black_box(class as u8));
+                    lthash.conditional_assign(black_box(&other), choice);
+                });
+                assert!(t < THRESHOLD, "t = {t}");
+            }
+        }
+    };
+}
+
+timing!(lthash16, lthash_rs::LtHash16<Shake128>);
+timing!(lthash20, lthash_rs::LtHash20<Shake128>);
+timing!(lthash32, lthash_rs::LtHash32<Shake128>);
@@ -0,0 +1,106 @@
+use lthash_rs::{LtHash, Transactional};
+use sha3::{CShake128, CShake128Core, Shake128};
+
+type LtHash16 = lthash_rs::LtHash16<Shake128>;
+
+#[test]
+fn commit() {
+    let mut lthash = LtHash16::from_iter(["apple"]);
+
+    let mut transaction = lthash.begin();
+    transaction.extend(["banana", "kiwi"]);
+    transaction.remove("apple");
+    transaction.union(&LtHash16::from_iter(["mango"]));
+    transaction.difference(&LtHash16::from_iter(["kiwi"]));
+    assert_eq!(
+        transaction.current(),
+        LtHash16::from_iter(["banana", "mango"])
+    );
+    transaction.commit();
+
+    assert_eq!(lthash, LtHash16::from_iter(["banana", "mango"]));
+}
+
+#[test]
+fn rollback() {
+    let mut lthash = LtHash16::from_iter(["apple"]);
+
+    let mut transaction = lthash.begin();
+    transaction.insert("banana");
+    transaction.rollback();
+    assert_eq!(lthash, LtHash16::from_iter(["apple"]));
+
+    let mut transaction = lthash.begin();
+    transaction.remove("apple");
+    drop(transaction);
+    assert_eq!(lthash, LtHash16::from_iter(["apple"]));
+}
+
+#[test]
+fn failing_batch() {
+    fn apply(lthash: &mut LtHash16, batch: &[&str]) -> Result<(), String> {
+        let mut transaction = lthash.begin();
+        for element in batch {
+            if element.is_empty() {
+                return Err(String::from("empty element"));
+            }
+            transaction.insert(element);
+        }
+        transaction.commit();
+        Ok(())
+    }
+
+    let mut lthash = LtHash16::new();
+    assert!(apply(&mut lthash, &["apple", "", "banana"]).is_err());
+    assert_eq!(lthash, LtHash16::new());
+    assert!(apply(&mut lthash, &["apple", "banana"]).is_ok());
+    assert_eq!(lthash, LtHash16::from_iter(["apple", "banana"]));
+}
+
+#[test]
+fn nested_savepoints() {
+    let mut lthash = LtHash16::new();
+
+    let mut transaction = lthash.begin();
+    transaction.insert("apple");
+    {
+        let mut savepoint = transaction.savepoint();
+        savepoint.insert("banana");
+        {
+            let mut nested = savepoint.savepoint();
+            nested.insert("kiwi");
+            // relative to the delta of the enclosing transaction
+            assert_eq!(
+                nested.current(),
+                LtHash16::from_iter(["banana", "kiwi"])
+            );
+        }
+        savepoint.commit();
+    }
+    {
+        let mut savepoint = transaction.savepoint();
+        savepoint.insert("mango");
+        savepoint.rollback();
+    }
+    assert_eq!(
+        transaction.delta(),
+        &LtHash16::from_iter(["apple", "banana"])
+    );
+    transaction.commit();
+
+    assert_eq!(lthash, LtHash16::from_iter(["apple", "banana"]));
+}
+
+#[test]
+fn delta_keeps_the_hasher() {
+    let hasher = CShake128::from_core(CShake128Core::new(b"customization"));
+    let mut lthash = lthash_rs::LtHash16::with_hasher(hasher.clone());
+    let mut expected = lthash.clone();
+    expected.insert("apple");
+
+    let mut transaction = lthash.begin();
+    transaction.insert("apple");
+    transaction.commit();
+
+    assert_eq!(lthash, expected);
+}
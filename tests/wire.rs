@@ -0,0 +1,129 @@
+use lthash_rs::{
+    Blake2xb, LtHash, LtHash16Blake2xb, LtHash20Blake2xb, LtHash32Blake2xb,
+};
+
+fn lthash16() -> LtHash16Blake2xb {
+    LtHash16Blake2xb::from_iter(["apple", "banana"])
+}
+
+#[test]
+fn roundtrip() {
+    let lthash = lthash16();
+
+    let frame = lthash.encode(None);
+    assert_eq!(frame.len(), 12 + 2048 + 4);
+    assert_eq!(
+        LtHash16Blake2xb::decode(&frame).unwrap(),
+        (lthash16(), None)
+    );
+
+    let frame = lthash.encode(Some(2));
+    assert_eq!(frame.len(), 12 + 8 + 2048 + 4);
+    assert_eq!(
+        LtHash16Blake2xb::decode(&frame).unwrap(),
+        (lthash16(), Some(2))
+    );
+
+    let lthash = LtHash20Blake2xb::from_iter(["apple"]);
+    let frame = lthash.encode(Some(1));
+    assert_eq!(LtHash20Blake2xb::decode(&frame).unwrap(), (lthash, Some(1)));
+
+    let lthash = LtHash32Blake2xb::from_iter(["apple"]);
+    let frame = lthash.encode(None);
+    assert_eq!(LtHash32Blake2xb::decode(&frame).unwrap(), (lthash, None));
+}
+
+#[test]
+fn header() {
+    let frame = lthash16().encode(Some(2));
+
+    assert_eq!(&frame[..4], b"LTHF");
+    assert_eq!(frame[4..12], [1, 16, 0, 4, 3, 0, 1, 0]);
+    assert_eq!(frame[12..20], 2u64.to_le_bytes());
+    assert_eq!(frame[20..2068], lthash16().into_bytes());
+    assert_eq!(frame[2068..], crc32fast::hash(&frame[..2068]).to_le_bytes());
+}
+
+#[test]
+fn corrupt_frame() {
+    let frame = lthash16().encode(None);
+
+    for i in [0, 4, 12, 1000, frame.len() - 1] {
+        let mut corrupt = frame.clone();
+        corrupt[i] ^= 1;
+        assert!(LtHash16Blake2xb::decode(&corrupt).is_err(), "byte {i}");
+    }
+
+    assert!(LtHash16Blake2xb::decode(&frame[..frame.len() - 1]).is_err());
+    assert!(LtHash16Blake2xb::decode(&frame[..8]).is_err());
+}
+
+#[test]
+fn mismatched_variant() {
+    let frame = lthash16().encode(None);
+    assert_eq!(
+        LtHash32Blake2xb::decode(&frame).unwrap_err(),
+        "Expected 1024 chunks of 32 bits, found 1024 chunks of 16 bits."
+    );
+}
+
+#[test]
+fn unknown_flags() {
+    let mut frame = lthash16().encode(None);
+    frame[10] = 2;
+    let len = frame.len() - 4;
+    let crc = crc32fast::hash(&frame[..len]);
+    frame[len..].copy_from_slice(&crc.to_le_bytes());
+
+    assert!(LtHash16Blake2xb::decode(&frame).is_err());
+}
+
+#[cfg(feature = "sha3")]
+#[test]
+fn mismatched_hash_function() {
+    let frame = lthash_rs::LtHash16Shake128::from_iter(["apple"]).encode(None);
+    assert_eq!(
+        LtHash16Blake2xb::decode(&frame).unwrap_err(),
+        "Expected hash function 3, found 1."
+    );
+}
+
+#[test]
+fn keyed_hasher() {
+    let hasher = Blake2xb::new(None, b"secret-key", b"", b"").unwrap();
+    let mut lthash = LtHash20Blake2xb::with_hasher(hasher.clone());
+    lthash.insert("apple");
+    let frame = lthash.encode(None);
+
+    let (mut decoded, _) =
+        LtHash20Blake2xb::decode_with_hasher(&frame, hasher).unwrap();
+    decoded.insert("banana");
+    lthash.insert("banana");
+    assert_eq!(decoded, lthash);
+
+    // the default hasher is not keyed
+    let (mut unkeyed, _) = LtHash20Blake2xb::decode(&frame).unwrap();
+    unkeyed.insert("banana");
+    assert_ne!(unkeyed, lthash);
+}
+
+#[cfg(feature = "sha3")]
+#[test]
+fn customized_hasher() {
+    use lthash_rs::LtHash16;
+    use sha3::{CShake128, CShake128Core};
+
+    let hasher = CShake128::from_core(CShake128Core::new(b"my-dataset"));
+    let mut lthash = LtHash16::with_hasher(hasher.clone());
+    lthash.insert("apple");
+    let frame = lthash.encode(Some(1));
+    assert_eq!(&frame[8..10], &6u16.to_le_bytes());
+
+    let (decoded, elements) =
+        LtHash16::decode_with_hasher(&frame, hasher).unwrap();
+    assert_eq!((decoded, elements), (lthash, Some(1)));
+    assert_eq!(
+        lthash_rs::LtHash16Shake128::decode(&frame).unwrap_err(),
+        "Expected hash function 1, found 6."
+    );
+}